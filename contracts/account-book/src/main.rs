@@ -9,29 +9,38 @@ ckb_std::entry!(program_entry);
 #[cfg(not(any(feature = "native-simulator", test)))]
 ckb_std::default_alloc!();
 
+// Args: Type ID | AccountBookData Hash
+
+use alloc::vec::Vec;
 use ckb_std::{
     ckb_constants::Source,
     ckb_types::prelude::{Builder, Entity, Pack, Reader, Unpack},
     error::SysError,
     high_level::{
-        load_cell_data, load_cell_lock, load_cell_type, load_cell_type_hash, load_script,
-        load_witness_args, QueryIter,
+        load_cell_capacity, load_cell_data, load_cell_lock, load_cell_lock_hash, load_cell_type,
+        load_cell_type_hash, load_input, load_script, load_script_hash, load_witness_args,
+        QueryIter,
     },
     log,
 };
-use core::panic;
 use spore_types::spore::{SporeData, SporeDataReader};
-use types::AccountBookData;
 use types::{error::SilentBerryError as Error, AccountBookCellData, AccountBookCellDataReader};
+use types::{
+    AccountBookData, AllowlistWitness, AllowlistWitnessReader, BuyIntentData, MemberLeaf,
+    MemberLeafVec, MemberLeafVecReader, WithdrawalIntentData, WithdrawalIntentDataReader,
+};
 use utils::{
-    account_book_proof::{SmtKey, TotalAmounts},
-    Hash, Level, UDTInfo,
+    account_book_proof::{
+        AccountBookProof, AllowlistProof, SmtKey, SmtLeaf, SmtValue, TotalAmounts,
+    },
+    profit::{self, ProfitConfig, Share},
+    Asset, Hash, Level, Since, UDTInfo,
 };
 
 fn load_verified_data() -> Result<AccountBookData, Error> {
     let args = load_script()?.args().raw_data();
-    if args.len() != utils::HASH_SIZE {
-        log::error!("Args len is not {} {}", utils::HASH_SIZE, args.len());
+    if args.len() != utils::HASH_SIZE * 2 {
+        log::error!("Args len is not {} {}", utils::HASH_SIZE * 2, args.len());
         return Err(Error::VerifiedData);
     }
     let witness = load_witness_args(0, Source::GroupOutput)?;
@@ -57,7 +66,7 @@ fn load_verified_data() -> Result<AccountBookData, Error> {
         .total_d(0.pack())
         .build();
     let hash = Hash::ckb_hash(data2.as_slice());
-    let intent_data_hash: Hash = args.try_into()?;
+    let intent_data_hash: Hash = args[utils::HASH_SIZE..].try_into()?;
 
     if hash != intent_data_hash {
         log::error!("Witness data Hash != Args");
@@ -67,29 +76,69 @@ fn load_verified_data() -> Result<AccountBookData, Error> {
     Ok(data)
 }
 
-fn load_verified_cell_data(is_selling: bool) -> Result<(AccountBookCellData, Hash), Error> {
+fn load_cell_data_pair() -> Result<(AccountBookCellData, AccountBookCellData), Error> {
     let old_data = load_cell_data(0, Source::GroupInput)?;
     let new_data = load_cell_data(0, Source::GroupOutput)?;
-
     AccountBookCellDataReader::verify(&old_data, true)?;
     AccountBookCellDataReader::verify(&new_data, true)?;
+    Ok((
+        AccountBookCellData::new_unchecked(old_data.into()),
+        AccountBookCellData::new_unchecked(new_data.into()),
+    ))
+}
 
-    let old_data = AccountBookCellData::new_unchecked(old_data.into());
-    let new_data = AccountBookCellData::new_unchecked(new_data.into());
+// The configuration the owner can update, the profit splits only until a spore is sold and
+// the quotas only lowered. The rest is the ledger, or fixed at creation.
+fn clear_config(data: &AccountBookCellData) -> AccountBookCellData {
+    data.clone()
+        .as_builder()
+        .platform_id(Default::default())
+        .asset_amount(Default::default())
+        .paused(Default::default())
+        .sale_start(Default::default())
+        .sale_end(Default::default())
+        .allowlist_root(Default::default())
+        .allowlist_limit(Default::default())
+        .a_num(Default::default())
+        .b_num(Default::default())
+        .c_num(Default::default())
+        .a_profit(Default::default())
+        .b_profit(Default::default())
+        .c_profit(Default::default())
+        .d_profit(Default::default())
+        .build()
+}
+
+// Only the configuration is changed, the ledger is the same
+fn is_update(old_data: &AccountBookCellData, new_data: &AccountBookCellData) -> bool {
+    old_data.as_slice() != new_data.as_slice()
+        && clear_config(old_data).as_slice() == clear_config(new_data).as_slice()
+}
+
+// The ledger, changed by selling and withdrawals
+fn clear_ledger(data: &AccountBookCellData) -> AccountBookCellData {
+    data.clone()
+        .as_builder()
+        .smt_root_hash(Default::default())
+        .member_count(0u32.pack())
+        .a_count(0u32.pack())
+        .b_count(0u32.pack())
+        .c_count(0u32.pack())
+        .a_share(Default::default())
+        .b_share(Default::default())
+        .c_share(Default::default())
+        .build()
+}
 
+// `sold` is the number of members joining in this transaction, by level A~D.
+fn load_verified_cell_data(
+    old_data: AccountBookCellData,
+    new_data: AccountBookCellData,
+    sold: [u32; 4],
+) -> Result<(AccountBookCellData, Hash), Error> {
     {
-        let tmp_old = old_data
-            .clone()
-            .as_builder()
-            .smt_root_hash(Default::default())
-            .member_count(0u32.pack())
-            .build();
-        let tmp_new = new_data
-            .clone()
-            .as_builder()
-            .smt_root_hash(Default::default())
-            .member_count(0u32.pack())
-            .build();
+        let tmp_old = clear_ledger(&old_data);
+        let tmp_new = clear_ledger(&new_data);
 
         if tmp_old.as_slice() != tmp_new.as_slice() {
             log::error!("Modification of CellData is not allowed");
@@ -97,62 +146,155 @@ fn load_verified_cell_data(is_selling: bool) -> Result<(AccountBookCellData, Has
         }
     }
 
+    // The shares are only raised by selling, and checked there
+    let new_members: u32 = sold.iter().sum();
+    if new_members == 0
+        && ProfitConfig::from(&old_data).shares != ProfitConfig::from(&new_data).shares
+    {
+        log::error!("CellData shares are modified without selling");
+        return Err(Error::AccountBookModified);
+    }
     let old_member_count: u32 = old_data.member_count().unpack();
     let new_member_count: u32 = new_data.member_count().unpack();
-    if is_selling {
-        if old_member_count + 1 != new_member_count {
+    if old_member_count.checked_add(new_members) != Some(new_member_count) {
+        log::error!(
+            "CellData member count incorrect: {} + {} != {}",
+            old_member_count,
+            new_members,
+            new_member_count
+        );
+        return Err(Error::AccountBookModified);
+    }
+
+    for (level, old_count, new_count, num, sold) in [
+        (
+            Level::A,
+            old_data.a_count(),
+            new_data.a_count(),
+            new_data.a_num(),
+            sold[0],
+        ),
+        (
+            Level::B,
+            old_data.b_count(),
+            new_data.b_count(),
+            new_data.b_num(),
+            sold[1],
+        ),
+        (
+            Level::C,
+            old_data.c_count(),
+            new_data.c_count(),
+            new_data.c_num(),
+            sold[2],
+        ),
+    ] {
+        let old_count: u32 = old_count.unpack();
+        let new_count: u32 = new_count.unpack();
+        let num: u32 = num.unpack();
+        if old_count.checked_add(sold) != Some(new_count) {
             log::error!(
-                "CellData member count incorrect: {}, {}",
-                old_member_count,
-                new_member_count
+                "CellData count of level {:?} incorrect: {} + {} != {}",
+                level,
+                old_count,
+                sold,
+                new_count
             );
             return Err(Error::AccountBookModified);
         }
-    } else if old_member_count != new_member_count {
-        log::error!("Withdrawal does not allow update member_count");
-        return Err(Error::AccountBookModified);
+        if new_count > num {
+            log::error!(
+                "Level {:?} is sold out, count: {}, num: {}",
+                level,
+                new_count,
+                num
+            );
+            return Err(Error::LevelSoldOut);
+        }
     }
 
     Ok((new_data, old_data.smt_root_hash().into()))
 }
 
-fn get_spore(source: Source) -> Result<(SporeData, Hash), Error> {
-    let mut spore_data = None;
-    let posion = QueryIter::new(load_cell_data, source).position(|cell_data| {
-        let r = SporeDataReader::verify(&cell_data, true).is_ok();
-        spore_data = Some(SporeData::new_unchecked(cell_data.into()));
-        r
-    });
+// The spores sold are the outputs with the spore type script of the AccountBook, any other
+// cell holding SporeData is not a spore.
+fn get_spores(
+    data: &AccountBookData,
+    source: Source,
+) -> Result<Vec<(SporeData, Hash, Level)>, Error> {
+    let spore_code_hash: Hash = data.spore_code_hash().into();
+    let spore_hash_type: u8 = data.spore_hash_type().into();
+    let level_offset: u8 = data.level_offset().into();
+    let level_width: u8 = data.level_width().into();
+    let mut spores = Vec::new();
+    for (index, type_script) in QueryIter::new(load_cell_type, source).enumerate() {
+        let type_script = match type_script {
+            Some(s)
+                if spore_code_hash == s.code_hash()
+                    && spore_hash_type == u8::from(s.hash_type()) =>
+            {
+                s
+            }
+            _ => continue,
+        };
 
-    if posion.is_some() && spore_data.is_some() {
-        let type_script_args = load_cell_type(posion.unwrap(), source)?
-            .ok_or_else(|| {
-                log::error!("Load Spore script is none");
-                Error::Spore
-            })?
-            .args();
+        let cell_data = load_cell_data(index, source)?;
+        SporeDataReader::verify(&cell_data, true)?;
+        let spore_data = SporeData::new_unchecked(cell_data.into());
+        let level = Level::from_spore(&spore_data, level_offset, level_width)?;
+        spores.push((spore_data, type_script.args().try_into()?, level));
+    }
 
-        Ok((spore_data.unwrap(), type_script_args.try_into()?))
-    } else {
+    if spores.is_empty() {
         log::error!("Spore Cell not found in {:?}", source);
-        Err(Error::Spore)
+        return Err(Error::Spore);
     }
+    Ok(spores)
 }
 
-fn check_script_code_hash(data: &AccountBookData) -> Result<bool, Error> {
-    let dob_selling_code_hash = data.dob_selling_code_hash().into();
+#[derive(PartialEq, Eq)]
+enum Operation {
+    Selling,
+    Withdrawal,
+    /// The Platform or Auther withdraws its profit.
+    Claim(Level),
+}
 
-    let has_dob_selling =
+fn check_operation(data: &AccountBookData) -> Result<Operation, Error> {
+    let dob_selling_code_hash = data.dob_selling_code_hash().into();
+    let withdrawal_code_hash = data.withdrawal_intent_code_hash().into();
+    let selling =
         !utils::get_index_by_code_hash(dob_selling_code_hash, true, Source::Input)?.is_empty();
-    if has_dob_selling {
-        Ok(true)
-    } else {
-        let withdrawal_code_hash = data.withdrawal_intent_code_hash().into();
-        let has_withdrawal =
-            !utils::get_index_by_code_hash(withdrawal_code_hash, false, Source::Input)?.is_empty();
-        if has_withdrawal {
-            Ok(false)
-        } else {
+    let withdrawal =
+        !utils::get_index_by_code_hash(withdrawal_code_hash, false, Source::Input)?.is_empty();
+    // A WithdrawalIntent is only settled by a withdrawal, otherwise it is burned unpaid
+    match (selling, withdrawal) {
+        (true, true) => {
+            log::error!("Selling and withdrawal cannot be in the same transaction");
+            return Err(Error::TxStructure);
+        }
+        (true, false) => return Ok(Operation::Selling),
+        (false, true) => return Ok(Operation::Withdrawal),
+        (false, false) => {}
+    }
+
+    let cell_data = load_cell_data(0, Source::GroupInput)?;
+    AccountBookCellDataReader::verify(&cell_data, true)?;
+    let cell_data = AccountBookCellData::new_unchecked(cell_data.into());
+
+    let auther_id: Hash = cell_data.auther_id().into();
+    let platform_id: Hash = cell_data.platform_id().into();
+    let has_auther = QueryIter::new(load_cell_lock_hash, Source::Input).any(|h| auther_id == h);
+    let has_platform = QueryIter::new(load_cell_lock_hash, Source::Input).any(|h| platform_id == h);
+
+    match (has_auther, has_platform) {
+        (true, false) => Ok(Operation::Claim(Level::Auther)),
+        (false, true) => Ok(Operation::Claim(Level::Platform)),
+        (true, true) => {
+            log::error!("Auther and Platform cannot withdraw in the same transaction");
+            Err(Error::TxStructure)
+        }
+        (false, false) => {
             log::error!("WithdrawalIntent Script not found in Inputs");
             Err(Error::CheckScript)
         }
@@ -184,11 +326,10 @@ fn check_account_book() -> Result<Hash, Error> {
     Ok(hash.into())
 }
 
-fn check_input_type_proxy_lock(
+fn load_input_type_proxy_lock(
     data: &AccountBookData,
     udt_info: &UDTInfo,
-    amount: u128,
-) -> Result<(u128, u128, u128), Error> {
+) -> Result<(u128, u128), Error> {
     let self_script_hash: Hash = load_cell_type_hash(0, Source::GroupInput)?
         .ok_or_else(|| {
             log::error!("Unknow Error: load cell type hash (Group Input)");
@@ -198,8 +339,8 @@ fn check_input_type_proxy_lock(
 
     let mut input_amount = None;
     let hash: Hash = data.input_type_proxy_lock_code_hash().into();
-    for (amount, index) in &udt_info.inputs {
-        let script = load_cell_lock(*index, Source::Input)?;
+    for cell in &udt_info.inputs {
+        let script = load_cell_lock(cell.index, Source::Input)?;
         if hash != script.code_hash() {
             continue;
         }
@@ -209,7 +350,7 @@ fn check_input_type_proxy_lock(
                 log::error!("Multiple input_type_proxy_locks found in Inputs");
                 return Err(Error::TxStructure);
             } else {
-                input_amount = Some(*amount);
+                input_amount = Some(cell.amount);
             }
         }
     }
@@ -219,8 +360,8 @@ fn check_input_type_proxy_lock(
     })?;
 
     let mut output_amount: Option<u128> = None;
-    for (amount, index) in &udt_info.outputs {
-        let script = load_cell_lock(*index, Source::Output)?;
+    for cell in &udt_info.outputs {
+        let script = load_cell_lock(cell.index, Source::Output)?;
         if hash != script.code_hash() {
             continue;
         }
@@ -230,7 +371,7 @@ fn check_input_type_proxy_lock(
                 log::error!("Multiple input_type_proxy_locks found in Outputs");
                 return Err(Error::TxStructure);
             } else {
-                output_amount = Some(*amount);
+                output_amount = Some(cell.amount);
             }
         }
     }
@@ -239,120 +380,815 @@ fn check_input_type_proxy_lock(
         Error::TxStructure
     })?;
 
-    if input_amount + amount != output_amount {
+    Ok((input_amount, output_amount))
+}
+
+fn load_withdrawal_intents(
+    data: &AccountBookData,
+) -> Result<Vec<(usize, WithdrawalIntentData)>, Error> {
+    let withdrawal_code_hash = data.withdrawal_intent_code_hash().into();
+    let indexs = utils::get_index_by_code_hash(withdrawal_code_hash, false, Source::Input)?;
+
+    let mut intents = Vec::with_capacity(indexs.len());
+    for index in indexs {
+        let withdrawal_data = load_witness_args(index, Source::Input)?
+            .input_type()
+            .to_opt()
+            .ok_or_else(|| {
+                log::error!("Load withdrawal intent witness failed, is none");
+                Error::TxStructure
+            })?
+            .raw_data()
+            .to_vec();
+        WithdrawalIntentDataReader::verify(&withdrawal_data, true)?;
+        intents.push((
+            index,
+            WithdrawalIntentData::new_unchecked(withdrawal_data.into()),
+        ));
+    }
+    Ok(intents)
+}
+
+// Old member leaves of the WithdrawalIntents, in the order of Inputs.
+fn load_member_leaves() -> Result<Vec<MemberLeaf>, Error> {
+    let witness = load_witness_args(0, Source::GroupInput)?
+        .input_type()
+        .to_opt()
+        .ok_or_else(|| {
+            log::error!("Load witnesses failed, input type is None");
+            Error::ParseWitness
+        })?
+        .raw_data();
+    MemberLeafVecReader::verify(&witness, false)?;
+    Ok(MemberLeafVec::new_unchecked(witness).into_iter().collect())
+}
+
+// The WithdrawalIntent in Inputs[index] is paid at its `change_location`, which also returns
+// the capacity of the intent to the owner. In CKB the payout is the capacity beyond it.
+fn load_intent_payout(
+    data: &AccountBookData,
+    index: usize,
+    intent: &WithdrawalIntentData,
+) -> Result<u128, Error> {
+    let change_location = {
+        let location: u32 = intent.change_location().unpack();
+        location as usize
+    };
+
+    let owner_script_hash: Hash = intent.owner_script_hash().into();
+    let lock_script_hash = match load_cell_lock_hash(change_location, Source::Output) {
+        Ok(hash) => hash,
+        Err(SysError::IndexOutOfBound) => {
+            log::error!(
+                "The payout of WithdrawalIntent not found, change location: {}",
+                change_location
+            );
+            return Err(Error::TxStructure);
+        }
+        Err(err) => return Err(err.into()),
+    };
+    if owner_script_hash != lock_script_hash {
         log::error!(
-            "In and Out Error: input: {}, output: {}, asset amount: {}",
-            input_amount,
-            output_amount,
-            amount
+            "The payout of WithdrawalIntent is not paid to the owner, change location: {}",
+            change_location
         );
-        return Err(Error::CheckXUDT);
+        return Err(Error::CheckScript);
+    }
+
+    let intent_capacity = load_cell_capacity(index, Source::Input)?;
+    let capacity = load_cell_capacity(change_location, Source::Output)?;
+    if capacity < intent_capacity {
+        log::error!(
+            "The capacity of WithdrawalIntent is not returned, intent: {}, returned: {}",
+            intent_capacity,
+            capacity
+        );
+        return Err(Error::CapacityError);
     }
 
-    let total_amounts: TotalAmounts = data.into();
+    let asset: Asset = data.xudt_script_hash().into();
+    let amount = asset
+        .load_amount(change_location, Source::Output)?
+        .ok_or_else(|| {
+            log::error!(
+                "The payout of WithdrawalIntent is not the asset, change location: {}",
+                change_location
+            );
+            Error::CheckXUDT
+        })?;
+    match asset {
+        Asset::XUDT(_) => Ok(amount),
+        Asset::CKB => Ok((capacity - intent_capacity) as u128),
+    }
+}
 
-    if input_amount != total_amounts.total() {
+fn load_vault_payout(data: &AccountBookData, udt_info: &UDTInfo) -> Result<u128, Error> {
+    let (input_amount, output_amount) = load_input_type_proxy_lock(data, udt_info)?;
+    input_amount.checked_sub(output_amount).ok_or_else(|| {
         log::error!(
-            "Witness total failed, input_amount: {}, a:{}, b:{}, c:{}, d:{}",
+            "Withdrawal cannot increase the vault, input: {}, output: {}",
             input_amount,
-            total_amounts.a,
-            total_amounts.b,
-            total_amounts.c,
-            total_amounts.d
+            output_amount
+        );
+        Error::CheckXUDT
+    })
+}
+
+fn check_payee(udt_info: &UDTInfo, lock_script_hash: Hash, amount: u128) -> Result<(), Error> {
+    let flow = udt_info.flow(&lock_script_hash);
+    let paid = match udt_info.asset {
+        Asset::XUDT(_) => flow.gained() == Some(amount),
+        // The payee signs the claim, and may pay the transaction fee out of the payout
+        Asset::CKB => flow.received(amount, udt_info.asset.max_fee()),
+    };
+    if !paid {
+        log::error!(
+            "Withdrawal is not paid to the owner, input: {}, output: {}, amount: {}",
+            flow.input,
+            flow.output,
+            amount
         );
         return Err(Error::CheckXUDT);
     }
 
-    Ok((input_amount, output_amount, amount))
+    Ok(())
 }
 
 fn is_creation() -> Result<bool, Error> {
-    Ok(false)
+    match load_cell_type_hash(0, Source::GroupInput) {
+        Ok(_) => Ok(false),
+        Err(SysError::IndexOutOfBound) => Ok(true),
+        Err(err) => {
+            log::error!("Load GroupInput type hash failed: {:?}", err);
+            Err(err.into())
+        }
+    }
+}
+
+fn is_paused(cell_data: &AccountBookCellData) -> Result<bool, Error> {
+    match u8::from(cell_data.paused()) {
+        0 => Ok(false),
+        1 => Ok(true),
+        paused => {
+            log::error!("The paused flag should be 0 or 1, but it is {}", paused);
+            Err(Error::VerifiedData)
+        }
+    }
+}
+
+// `sale_start` and `sale_end`, `None` if not set
+fn load_sale_window(
+    cell_data: &AccountBookCellData,
+) -> Result<(Option<Since>, Option<Since>), Error> {
+    let load = |value: types::blockchain::Uint64| -> Result<Option<Since>, Error> {
+        let value: u64 = value.unpack();
+        if value == 0 {
+            return Ok(None);
+        }
+        let since = Since::try_from(value)?;
+        if !since.is_absolute() {
+            log::error!("The sale window should be absolute since: {:#x}", value);
+            return Err(Error::SaleWindow);
+        }
+        Ok(Some(since))
+    };
+    let sale_start = load(cell_data.sale_start())?;
+    let sale_end = load(cell_data.sale_end())?;
+    if let (Some(start), Some(end)) = (&sale_start, &sale_end) {
+        if !end.reaches(start)? {
+            log::error!(
+                "The sale ends before it starts, start: {:#x}, end: {:#x}",
+                start.as_u64(),
+                end.as_u64()
+            );
+            return Err(Error::SaleWindow);
+        }
+    }
+    Ok((sale_start, sale_end))
+}
+
+// A transaction can only prove it is not before a time, by the since of the AccountBook
+// input, so `sale_end` here only rejects a since past it. A late sale with an earlier since
+// passes, it is stopped only once someone submits `close_sale`.
+fn check_sale_window(cell_data: &AccountBookCellData) -> Result<(), Error> {
+    let (sale_start, sale_end) = load_sale_window(cell_data)?;
+    if sale_start.is_none() && sale_end.is_none() {
+        return Ok(());
+    }
+
+    let since = Since::load_input(0, Source::GroupInput)?;
+    if let Some(start) = sale_start {
+        if !since.reaches(&start)? {
+            log::error!(
+                "The sale has not started, since: {:#x}, start: {:#x}",
+                since.as_u64(),
+                start.as_u64()
+            );
+            return Err(Error::SaleWindow);
+        }
+    }
+    if let Some(end) = sale_end {
+        if since.reaches(&end)? {
+            log::error!(
+                "The sale has ended, since: {:#x}, end: {:#x}",
+                since.as_u64(),
+                end.as_u64()
+            );
+            return Err(Error::SaleWindow);
+        }
+    }
+    Ok(())
+}
+
+fn check_profit(cell_data: &AccountBookCellData) -> Result<(), Error> {
+    let profits = [
+        cell_data.a_profit().raw_data(),
+        cell_data.b_profit().raw_data(),
+        cell_data.c_profit().raw_data(),
+        cell_data.d_profit().raw_data(),
+    ];
+    for profit in profits {
+        let sum: u32 = profit.iter().map(|p| *p as u32).sum();
+        if sum != 100 {
+            log::error!("The sum of profit must be 100, {:?} is {}", profit, sum);
+            return Err(Error::VerifiedData);
+        }
+    }
+    Ok(())
+}
+
+// A level without quota never has members to take its member column
+fn check_member_columns(cell_data: &AccountBookCellData) -> Result<(), Error> {
+    let config = ProfitConfig::from(cell_data);
+    for (index, level) in ["A", "B", "C"].into_iter().enumerate() {
+        if config.nums[index] == 0 && config.profits.iter().any(|p| p[index + 2] != 0) {
+            log::error!(
+                "Level {} has no quota, but its member column is not 0",
+                level
+            );
+            return Err(Error::VerifiedData);
+        }
+    }
+    Ok(())
+}
+
+fn check_cluster(data: &AccountBookData, owner_script_hash: Hash) -> Result<(), Error> {
+    let cluster_code_hash: Hash = data.cluster_code_hash().into();
+    let cluster_id: Hash = data.cluster_id().into();
+
+    let pos = QueryIter::new(load_cell_type, Source::Input).position(|script| {
+        if let Some(script) = script {
+            cluster_code_hash == script.code_hash()
+                && cluster_id == script.args().raw_data().as_ref()
+        } else {
+            false
+        }
+    });
+    let pos = pos.ok_or_else(|| {
+        log::error!("Cluster not found in Inputs");
+        Error::CheckScript
+    })?;
+
+    if owner_script_hash != load_cell_lock_hash(pos, Source::Input)? {
+        log::error!("The Cluster is not owned by the AccountBook owner");
+        return Err(Error::CheckScript);
+    }
+
+    Ok(())
+}
+
+// The Type ID is the hash of the first input and the output index, so no other cell can
+// ever be created with the same args.
+fn check_type_id() -> Result<(), Error> {
+    let args = load_script()?.args().raw_data();
+    let type_id: Hash = args[..utils::HASH_SIZE].try_into()?;
+
+    let script_hash = load_script_hash()?;
+    let index = QueryIter::new(load_cell_type_hash, Source::Output)
+        .position(|type_hash| type_hash == Some(script_hash))
+        .ok_or_else(|| {
+            log::error!("AccountBook not found in Output");
+            Error::CheckScript
+        })?;
+    let input = load_input(0, Source::Input)?;
+    let hash = Hash::ckb_hash(&[input.as_slice(), &(index as u64).to_le_bytes()].concat());
+    if hash != type_id {
+        log::error!("The Type ID of new AccountBook is incorrect");
+        return Err(Error::VerifiedData);
+    }
+
+    Ok(())
+}
+
+fn creation(data: AccountBookData) -> Result<(), Error> {
+    let ret = load_cell_type_hash(1, Source::GroupOutput);
+    if ret.is_ok() || ret.unwrap_err() != SysError::IndexOutOfBound {
+        log::error!("Multiple AccountBook found in Output");
+        return Err(Error::TxStructure);
+    }
+    check_type_id()?;
+
+    let cell_data = load_cell_data(0, Source::GroupOutput)?;
+    AccountBookCellDataReader::verify(&cell_data, true)?;
+    let cell_data = AccountBookCellData::new_unchecked(cell_data.into());
+
+    for count in [
+        cell_data.member_count(),
+        cell_data.a_count(),
+        cell_data.b_count(),
+        cell_data.c_count(),
+    ] {
+        let count: u32 = count.unpack();
+        if count != 0 {
+            log::error!("The member count of new AccountBook must be 0, {}", count);
+            return Err(Error::VerifiedData);
+        }
+    }
+    if ProfitConfig::from(&cell_data).shares != [Share::default(); 3] {
+        log::error!("The shares of new AccountBook must be 0");
+        return Err(Error::VerifiedData);
+    }
+
+    let smt_root_hash: Hash = cell_data.smt_root_hash().into();
+    if smt_root_hash != utils::account_book_proof::genesis_root()? {
+        log::error!("The SMT root of new AccountBook is not the genesis root");
+        return Err(Error::Smt);
+    }
+
+    let level_width: u8 = data.level_width().into();
+    if level_width == 0 || level_width > 8 {
+        log::error!("DNA level width should be 1~8, but it is {}", level_width);
+        return Err(Error::VerifiedData);
+    }
+
+    is_paused(&cell_data)?;
+    load_sale_window(&cell_data)?;
+    check_profit(&cell_data)?;
+    check_member_columns(&cell_data)?;
+    check_cluster(&data, cell_data.owner_script_hash().into())?;
+
+    Ok(())
 }
 
-fn creation(_data: AccountBookData) -> Result<(), Error> {
-    panic!("Unsuppore");
+// The buyers of the BuyIntents for this AccountBook, with the number of spores each buys
+fn load_buyers(data: &AccountBookData) -> Result<Vec<(Hash, u32)>, Error> {
+    let buy_intent_code_hash: Hash = data.buy_intent_code_hash().into();
+    let self_script_hash = load_script_hash()?;
+
+    let mut buyers: Vec<(Hash, u32)> = Vec::new();
+    for (index, type_script) in QueryIter::new(load_cell_type, Source::Input).enumerate() {
+        let args = match type_script {
+            Some(s) if buy_intent_code_hash == s.code_hash() => s.args().raw_data(),
+            _ => continue,
+        };
+        if args.len() != utils::HASH_SIZE * 2 || args[..utils::HASH_SIZE] != self_script_hash {
+            continue;
+        }
+
+        let witness = load_witness_args(index, Source::Input)?
+            .input_type()
+            .to_opt()
+            .ok_or_else(|| {
+                log::error!("Load witnesses of BuyIntent {} failed", index);
+                Error::ParseWitness
+            })?
+            .raw_data();
+        types::BuyIntentDataReader::verify(&witness, false)?;
+        let intent = BuyIntentData::new_unchecked(witness);
+        if Hash::ckb_hash(intent.as_slice()) != &args[utils::HASH_SIZE..] {
+            log::error!("Check BuyIntent data hash failed");
+            return Err(Error::VerifiedData);
+        }
+
+        let owner_script_hash: Hash = intent.owner_script_hash().into();
+        match buyers
+            .iter_mut()
+            .find(|(hash, _)| *hash == owner_script_hash)
+        {
+            Some((_, count)) => *count += 1,
+            None => buyers.push((owner_script_hash, 1)),
+        }
+    }
+    Ok(buyers)
+}
+
+// With an allowlist, every spore is bought by a buyer in it, and the SMT leaves of the buyer
+// counts are returned.
+fn check_allowlist(
+    data: &AccountBookData,
+    cell_data: &AccountBookCellData,
+    spores: usize,
+) -> Result<Vec<SmtLeaf>, Error> {
+    let allowlist_root: Hash = cell_data.allowlist_root().into();
+    if allowlist_root == [0u8; 32] {
+        return Ok(Vec::new());
+    }
+
+    let buyers = load_buyers(data)?;
+    let bought: u32 = buyers.iter().map(|(_, count)| count).sum();
+    if bought as usize != spores {
+        log::error!(
+            "Spores are not all bought by BuyIntents, spores: {}, BuyIntents: {}",
+            spores,
+            bought
+        );
+        return Err(Error::Allowlist);
+    }
+
+    let witness = load_witness_args(0, Source::GroupInput)?
+        .input_type()
+        .to_opt()
+        .ok_or_else(|| {
+            log::error!("Load witnesses failed, input type is None");
+            Error::ParseWitness
+        })?
+        .raw_data();
+    AllowlistWitnessReader::verify(&witness, false)?;
+    let witness = AllowlistWitness::new_unchecked(witness);
+    if witness.buyers().len() != buyers.len() {
+        log::error!(
+            "The number of buyer counts ({}) does not match buyers ({})",
+            witness.buyers().len(),
+            buyers.len()
+        );
+        return Err(Error::ParseWitness);
+    }
+
+    let buyer_hashes: Vec<Hash> = buyers.iter().map(|(hash, _)| hash.clone()).collect();
+    AllowlistProof::new(witness.proof().unpack()).verify(allowlist_root, &buyer_hashes)?;
+
+    let limit: u32 = cell_data.allowlist_limit().unpack();
+    let mut leaves = Vec::with_capacity(buyers.len());
+    for ((owner_script_hash, count), old) in buyers.into_iter().zip(witness.buyers()) {
+        if owner_script_hash != old.owner_script_hash() {
+            log::error!("The buyer counts are not in the order of BuyIntents");
+            return Err(Error::ParseWitness);
+        }
+        let old_count: u32 = old.count().unpack();
+        let new_count = old_count.checked_add(count).ok_or_else(|| {
+            log::error!("Buyer count overflow");
+            Error::AccountBookOverflow
+        })?;
+        if limit != 0 && new_count > limit {
+            log::error!(
+                "Buyer exceeds the allowlist limit, bought: {}, limit: {}",
+                new_count,
+                limit
+            );
+            return Err(Error::Allowlist);
+        }
+        leaves.push(SmtLeaf::new(
+            SmtKey::Buyer(owner_script_hash),
+            (old_count != 0).then(|| SmtValue::new(old_count as u128)),
+            Some(SmtValue::new(new_count as u128)),
+        ));
+    }
+    Ok(leaves)
 }
 
 fn selling(
     data: AccountBookData,
     cell_data: AccountBookCellData,
     old_smt_hash: Hash,
+    old_config: ProfitConfig,
+    spores: Vec<(SporeData, Hash, Level)>,
 ) -> Result<(), Error> {
-    let (spore_data, spore_id) = get_spore(Source::Output)?;
+    if is_paused(&cell_data)? {
+        log::error!("Selling is paused");
+        return Err(Error::Paused);
+    }
+    check_sale_window(&cell_data)?;
+
+    // Each spore is sold by one DobSelling
+    let dob_selling_code_hash = data.dob_selling_code_hash().into();
+    let dob_sellings = utils::get_index_by_code_hash(dob_selling_code_hash, true, Source::Input)?;
+    if dob_sellings.len() != spores.len() {
+        log::error!(
+            "The number of spores ({}) does not match DobSellings ({})",
+            spores.len(),
+            dob_sellings.len()
+        );
+        return Err(Error::TxStructure);
+    }
+    let buyer_leaves = check_allowlist(&data, &cell_data, spores.len())?;
 
     // check cluster id
-    if spore_data
-        .cluster_id()
-        .to_opt()
-        .ok_or_else(|| {
-            log::error!("Cluster ID is None in Spore Data");
-            Error::Spore
-        })?
-        .raw_data()
-        != data.cluster_id().as_slice()
-    {
-        log::error!("The cluster id does not match");
-        return Err(Error::VerifiedData);
+    for (spore_data, _, _) in &spores {
+        if spore_data
+            .cluster_id()
+            .to_opt()
+            .ok_or_else(|| {
+                log::error!("Cluster ID is None in Spore Data");
+                Error::Spore
+            })?
+            .raw_data()
+            != data.cluster_id().as_slice()
+        {
+            log::error!("The cluster id does not match");
+            return Err(Error::VerifiedData);
+        }
     }
 
     let udt_info = utils::UDTInfo::new(data.xudt_script_hash().into())?;
     udt_info.check_udt()?;
 
-    let (_, _, amount) =
-        check_input_type_proxy_lock(&data, &udt_info, cell_data.asset_amount().unpack())?;
+    let asset_amount: u128 = cell_data.asset_amount().unpack();
+    let amount = asset_amount
+        .checked_mul(spores.len() as u128)
+        .ok_or_else(|| {
+            log::error!("Selling amount overflow");
+            Error::AccountBookOverflow
+        })?;
+    let (input_amount, output_amount) = load_input_type_proxy_lock(&data, &udt_info)?;
+    if input_amount.checked_add(amount) != Some(output_amount) {
+        log::error!(
+            "In and Out Error: input: {}, output: {}, asset amount: {}",
+            input_amount,
+            output_amount,
+            amount
+        );
+        return Err(Error::CheckXUDT);
+    }
 
     let mut total: TotalAmounts = (&data).into();
-    let proof = utils::account_book_proof::AccountBookProof::new(data.proof().unpack());
-    if !proof.verify(
-        old_smt_hash,
-        total.clone(),
-        (SmtKey::Member(spore_id.clone()), None),
-    )? {
-        log::error!("Verify Input SMT failed");
-        return Err(Error::Smt);
+    // Withdrawals are paid out of the vault, so it never holds more than the totals.
+    if input_amount > total.total()? {
+        log::error!(
+            "Witness total failed, input_amount: {}, a:{}, b:{}, c:{}, d:{}",
+            input_amount,
+            total.a,
+            total.b,
+            total.c,
+            total.d
+        );
+        return Err(Error::CheckXUDT);
     }
 
-    let level: Level = spore_data.try_into()?;
-    total.add(amount, level)?;
+    // Members are numbered in the order they joined
+    let member_count: u32 = cell_data.member_count().unpack();
+    let first_joined_at = member_count - spores.len() as u32;
+    let mut leaves = Vec::with_capacity(spores.len() + 4);
+    for (joined_at, (_, spore_id, level)) in (first_joined_at..).zip(spores) {
+        total.add(asset_amount, level)?;
+        let share_index = profit::share_index(&old_config.shares, level)?;
+        leaves.push(SmtLeaf::new(
+            SmtKey::Member(spore_id),
+            None,
+            Some(SmtValue::new_member(level, 0, joined_at, share_index)),
+        ));
+    }
+    let old_total: TotalAmounts = (&data).into();
 
-    let new_smt_hash: Hash = cell_data.smt_root_hash().into();
-    if !proof.verify(new_smt_hash, total, (SmtKey::Member(spore_id), Some(0)))? {
-        log::error!("Verify Output SMT failed");
-        return Err(Error::Smt);
+    // The new members share the income of this selling
+    let config = ProfitConfig::from(&cell_data);
+    let shares = config.next_shares(&old_config, &old_total, &total)?;
+    if shares != config.shares {
+        log::error!(
+            "CellData shares incorrect, expected: {:?}, actual: {:?}",
+            shares,
+            config.shares
+        );
+        return Err(Error::AccountBookModified);
     }
 
-    Ok(())
+    leaves.extend(old_total.leaves(&total));
+    leaves.extend(buyer_leaves);
+
+    AccountBookProof::new(data.proof().unpack()).verify(
+        old_smt_hash,
+        cell_data.smt_root_hash().into(),
+        &leaves,
+    )
 }
 
-fn withdrawal(data: AccountBookData) -> Result<(), Error> {
-    let xudt_script_hash = data.xudt_script_hash().into();
-    let udt_info = UDTInfo::new(xudt_script_hash)?;
-    udt_info.check_udt()?;
+// Each withdrawal is (key, level, value, amount). The leaf records the cumulative withdrawn
+// amount: it becomes the whole entitlement, and was `amount` less before.
+fn settle_withdrawal(
+    data: &AccountBookData,
+    cell_data: &AccountBookCellData,
+    old_smt_hash: Hash,
+    payout: u128,
+    withdrawals: Vec<(SmtKey, Level, SmtValue, u128)>,
+) -> Result<(), Error> {
+    let total: TotalAmounts = data.into();
+    let config = ProfitConfig::from(cell_data);
 
-    // Load spore level
-    let _spore_level: u8 = {
-        let withdrawal_code_hash = data.withdrawal_intent_code_hash().into();
-        let indexs = utils::get_index_by_code_hash(withdrawal_code_hash, false, Source::Input)?;
-        let withdrawal_data = load_witness_args(indexs[0], Source::Input)?
-            .input_type()
-            .to_opt()
+    let mut amount = 0u128;
+    let mut leaves = Vec::with_capacity(withdrawals.len() + 4);
+    for (key, level, value, withdrawal_amount) in withdrawals {
+        amount = amount.checked_add(withdrawal_amount).ok_or_else(|| {
+            log::error!("Withdrawal amount overflow");
+            Error::AccountBookOverflow
+        })?;
+
+        let new_withdrawn = config.get_withdrawal(level, value.share_index(), &total)?;
+        let old_withdrawn = new_withdrawn
+            .checked_sub(withdrawal_amount)
             .ok_or_else(|| {
-                log::error!("Load withdrawal intent witness failed, is none");
-                Error::TxStructure
-            })?
-            .raw_data()
-            .to_vec();
-        types::WithdrawalIntentDataReader::verify(&withdrawal_data, true)?;
-        types::WithdrawalIntentData::new_unchecked(withdrawal_data.into())
-            .spore_level()
-            .into()
+                log::error!(
+                    "Withdrawal exceeds the entitlement: {}, amount: {}",
+                    new_withdrawn,
+                    withdrawal_amount
+                );
+                Error::AccountBookOverflow
+            })?;
+        leaves.push(SmtLeaf::new(
+            key,
+            Some(value.with_amount(old_withdrawn)),
+            Some(value.with_amount(new_withdrawn)),
+        ));
+    }
+
+    if amount != payout {
+        log::error!(
+            "The vault payout does not match the withdrawals, payout: {}, withdrawals: {}",
+            payout,
+            amount
+        );
+        return Err(Error::CheckXUDT);
+    }
+
+    leaves.extend(total.leaves(&total));
+
+    AccountBookProof::new(data.proof().unpack()).verify(
+        old_smt_hash,
+        cell_data.smt_root_hash().into(),
+        &leaves,
+    )
+}
+
+fn withdrawal(
+    data: AccountBookData,
+    cell_data: AccountBookCellData,
+    old_smt_hash: Hash,
+) -> Result<(), Error> {
+    let udt_info = UDTInfo::new(data.xudt_script_hash().into())?;
+    udt_info.check_udt()?;
+    let payout = load_vault_payout(&data, &udt_info)?;
+
+    let intents = load_withdrawal_intents(&data)?;
+    let member_leaves = load_member_leaves()?;
+    if member_leaves.len() != intents.len() {
+        log::error!(
+            "The number of member leaves ({}) does not match WithdrawalIntents ({})",
+            member_leaves.len(),
+            intents.len()
+        );
+        return Err(Error::ParseWitness);
+    }
+
+    let mut spore_ids: Vec<Hash> = Vec::new();
+    let mut change_locations: Vec<u32> = Vec::new();
+    let mut withdrawals = Vec::new();
+    for ((index, withdrawal_data), leaf) in intents.into_iter().zip(member_leaves) {
+        // The level committed in the SMT, not the one claimed by the intent
+        let level: Level = leaf.level().try_into()?;
+        let spore_id: Hash = withdrawal_data.spore_id().into();
+        if spore_ids.contains(&spore_id) {
+            log::error!("Multiple WithdrawalIntents of the same Spore");
+            return Err(Error::TxStructure);
+        }
+        spore_ids.push(spore_id.clone());
+        // The intent checked its spore against this type
+        if withdrawal_data.spore_code_hash().as_slice() != data.spore_code_hash().as_slice()
+            || withdrawal_data.spore_hash_type().as_slice() != data.spore_hash_type().as_slice()
+        {
+            log::error!("The spore of WithdrawalIntent is not the type of this AccountBook");
+            return Err(Error::Spore);
+        }
+        let change_location: u32 = withdrawal_data.change_location().unpack();
+        if change_locations.contains(&change_location) {
+            log::error!("Multiple WithdrawalIntents paid by the same output");
+            return Err(Error::TxStructure);
+        }
+        change_locations.push(change_location);
+
+        let amount = load_intent_payout(&data, index, &withdrawal_data)?;
+        withdrawals.push((
+            SmtKey::Member(spore_id),
+            level,
+            SmtValue::Member(leaf),
+            amount,
+        ));
+    }
+
+    settle_withdrawal(&data, &cell_data, old_smt_hash, payout, withdrawals)
+}
+
+fn claim(
+    data: AccountBookData,
+    cell_data: AccountBookCellData,
+    old_smt_hash: Hash,
+    level: Level,
+) -> Result<(), Error> {
+    let (key, payee) = match level {
+        Level::Auther => (SmtKey::Auther, cell_data.auther_id().into()),
+        Level::Platform => (SmtKey::Platform, cell_data.platform_id().into()),
+        _ => {
+            log::error!("Only Auther or Platform can claim profit, {:?}", level);
+            return Err(Error::Unknow);
+        }
     };
 
-    // TODO
+    let udt_info = UDTInfo::new(data.xudt_script_hash().into())?;
+    udt_info.check_udt()?;
+    let payout = load_vault_payout(&data, &udt_info)?;
+    check_payee(&udt_info, payee, payout)?;
+
+    settle_withdrawal(
+        &data,
+        &cell_data,
+        old_smt_hash,
+        payout,
+        alloc::vec![(key, level, SmtValue::new(0), payout)],
+    )
+}
+
+fn update(
+    data: AccountBookData,
+    old_data: AccountBookCellData,
+    new_data: AccountBookCellData,
+) -> Result<(), Error> {
+    let owner_script_hash: Hash = old_data.owner_script_hash().into();
+    if !QueryIter::new(load_cell_lock_hash, Source::Input).any(|h| owner_script_hash == h) {
+        close_sale(&old_data, &new_data)?;
+    }
 
-    // let spore_level = get_spore_level(data);
+    // Nothing is settled when updating: no selling, no withdrawal, and the vault is not spent
+    for (code_hash, is_lock) in [
+        (data.dob_selling_code_hash(), true),
+        (data.withdrawal_intent_code_hash(), false),
+    ] {
+        if !utils::get_index_by_code_hash(code_hash.into(), is_lock, Source::Input)?.is_empty() {
+            log::error!("Selling or withdrawal is not allowed when updating");
+            return Err(Error::TxStructure);
+        }
+    }
+    let self_script_hash = load_script_hash()?;
+    let proxy_lock_code_hash: Hash = data.input_type_proxy_lock_code_hash().into();
+    if QueryIter::new(load_cell_lock, Source::Input).any(|lock| {
+        proxy_lock_code_hash == lock.code_hash()
+            && lock.args().raw_data().as_ref() == self_script_hash
+    }) {
+        log::error!("The vault can not be spent when updating");
+        return Err(Error::TxStructure);
+    }
 
+    // The owner closes a level by lowering its quota, but not below its members
+    let old_config = ProfitConfig::from(&old_data);
+    let new_config = ProfitConfig::from(&new_data);
+    for (index, level) in ["A", "B", "C"].into_iter().enumerate() {
+        let num = new_config.nums[index];
+        if num > old_config.nums[index] || num < new_config.counts[index] {
+            log::error!(
+                "The quota of level {} can only be lowered to its members, {} -> {}, members: {}",
+                level,
+                old_config.nums[index],
+                num,
+                new_config.counts[index]
+            );
+            return Err(Error::VerifiedData);
+        }
+    }
+
+    // The shares already sold were divided by the old splits
+    let member_count: u32 = old_data.member_count().unpack();
+    if member_count != 0 {
+        if old_config.profits != new_config.profits {
+            log::error!("Profit can not be updated after selling");
+            return Err(Error::VerifiedData);
+        }
+    } else {
+        check_member_columns(&new_data)?;
+    }
+
+    is_paused(&new_data)?;
+    load_sale_window(&new_data)?;
+    check_profit(&new_data)
+}
+
+// Without the owner, anyone can pause the selling once `sale_end` is reached, the only
+// way `sale_end` is enforced
+fn close_sale(old_data: &AccountBookCellData, new_data: &AccountBookCellData) -> Result<(), Error> {
+    let (_, sale_end) = load_sale_window(old_data)?;
+    let closed = old_data.clone().as_builder().paused(1.into()).build();
+    let sale_end = match sale_end {
+        Some(end) if closed.as_slice() == new_data.as_slice() => end,
+        _ => {
+            log::error!("Update failed, owner not found in Inputs");
+            return Err(Error::CheckScript);
+        }
+    };
+
+    let since = Since::load_input(0, Source::GroupInput)?;
+    if !since.reaches(&sale_end)? {
+        log::error!(
+            "The sale has not ended, since: {:#x}, end: {:#x}",
+            since.as_u64(),
+            sale_end.as_u64()
+        );
+        return Err(Error::SaleWindow);
+    }
     Ok(())
 }
 
@@ -363,15 +1199,28 @@ fn program_entry2() -> Result<(), Error> {
     }
 
     check_account_book()?;
-    let is_selling = check_script_code_hash(&data)?;
-    let (cell_data, old_smt_hash) = load_verified_cell_data(is_selling)?;
-    if is_selling {
-        selling(data, cell_data, old_smt_hash)?;
-    } else {
-        withdrawal(data)?;
+    let (old_cell_data, new_cell_data) = load_cell_data_pair()?;
+    if is_update(&old_cell_data, &new_cell_data) {
+        return update(data, old_cell_data, new_cell_data);
     }
 
-    Ok(())
+    let old_config = ProfitConfig::from(&old_cell_data);
+    let operation = check_operation(&data)?;
+    let spores = if operation == Operation::Selling {
+        get_spores(&data, Source::Output)?
+    } else {
+        Vec::new()
+    };
+    let mut sold = [0u32; 4];
+    for (_, _, level) in &spores {
+        sold[*level as usize - 1] += 1;
+    }
+    let (cell_data, old_smt_hash) = load_verified_cell_data(old_cell_data, new_cell_data, sold)?;
+    match operation {
+        Operation::Selling => selling(data, cell_data, old_smt_hash, old_config, spores),
+        Operation::Withdrawal => withdrawal(data, cell_data, old_smt_hash),
+        Operation::Claim(level) => claim(data, cell_data, old_smt_hash, level),
+    }
 }
 
 pub fn program_entry() -> i8 {
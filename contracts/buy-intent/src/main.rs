@@ -16,14 +16,15 @@ use ckb_std::{
     ckb_types::prelude::{Entity, Reader, Unpack},
     error::SysError,
     high_level::{
-        load_cell_capacity, load_cell_data, load_cell_lock_hash, load_cell_type_hash,
-        load_input_since, load_script, load_witness_args, QueryIter,
+        load_cell_capacity, load_cell_data, load_cell_data_hash, load_cell_lock,
+        load_cell_lock_hash, load_cell_occupied_capacity, load_cell_type, load_cell_type_hash,
+        load_script, load_witness_args, QueryIter,
     },
     log::{self},
 };
 use types::error::SilentBerryError as Error;
-use types::{AccountBookCellData, BuyIntentData};
-use utils::Hash;
+use types::{AccountBookCellData, BuyIntentData, DobSellingData, DobSellingDataReader};
+use utils::{Asset, Hash, Since};
 
 fn is_input() -> Result<bool, Error> {
     let input = match load_cell_capacity(0, Source::GroupInput) {
@@ -106,23 +107,10 @@ fn load_verified_data(is_input: bool) -> Result<(BuyIntentData, Hash), Error> {
     Ok((data, args[..utils::HASH_SIZE].try_into()?))
 }
 
-fn check_input_dob_selling(dob_selling_hash: Hash) -> Result<(), Error> {
-    if QueryIter::new(load_cell_lock_hash, Source::Input).any(|f| dob_selling_hash == f) {
-        Ok(())
-    } else {
-        log::error!("DobSelling not found");
-        Err(Error::CheckScript)
-    }
-}
-
 fn check_account_book(account_book_hash: Hash, amount: u128) -> Result<(), Error> {
-    let mut count = 0;
-    QueryIter::new(load_cell_type_hash, Source::Input).all(|f| {
-        if account_book_hash == f.unwrap() {
-            count += 1;
-        }
-        true
-    });
+    let count = QueryIter::new(load_cell_type_hash, Source::Input)
+        .filter(|f| account_book_hash == *f)
+        .count();
     if count != 1 {
         log::error!(
             "AccountBook quantity error in Input, Need 1, Found {}",
@@ -159,90 +147,331 @@ fn check_account_book(account_book_hash: Hash, amount: u128) -> Result<(), Error
     Ok(())
 }
 
-fn program_entry2() -> Result<(), Error> {
-    let is_input = is_input()?;
-    let (data, accountbook_hash) = load_verified_data(is_input)?;
-
-    // Check xUDT Script Hash
-    let xudt_script_hash: Hash = data.xudt_script_hash().into();
+// The asset paid to the DobSelling
+fn check_payment(data: &BuyIntentData) -> Result<(), Error> {
+    let udt_info = utils::UDTInfo::new(data.xudt_script_hash().into())?;
+    udt_info.check_udt()?;
+
+    let dob_selling_script_hash: Hash = data.dob_selling_script_hash().into();
+    let payments = udt_info
+        .outputs
+        .iter()
+        .filter(|cell| cell.lock_script_hash == dob_selling_script_hash)
+        .count();
+    if payments != 1 {
+        log::error!(
+            "xUDT of DobSelling quantity error in Output, Need 1, Found {}",
+            payments
+        );
+        return Err(Error::CheckXUDT);
+    }
 
-    if is_input {
-        let ret = check_account_book(accountbook_hash, data.asset_amount().unpack());
-        if ret.is_ok() {
-            check_input_dob_selling(data.dob_selling_script_hash().into())?;
-            Ok(())
-        } else {
-            let since = load_input_since(0, Source::GroupInput)?;
-            let expire_since: u64 = data.expire_since().unpack();
-            if since < expire_since {
-                return ret;
-            }
+    // The seller gains exactly the asset amount
+    let asset_amount: u128 = data.asset_amount().unpack();
+    let flow = udt_info.flow(&dob_selling_script_hash);
+    if flow.gained() != Some(asset_amount) {
+        log::error!(
+            "Incorrect xUDT payment: Need: {}, Actually: input: {}, output: {}",
+            asset_amount,
+            flow.input,
+            flow.output
+        );
+        return Err(Error::CheckXUDT);
+    }
 
-            let owner_script_hash: Hash = data.owner_script_hash().into();
-            let lock_script_hash = load_cell_lock_hash(1, Source::Output)?;
-            if owner_script_hash != lock_script_hash {
-                log::error!("Revocation failed, not found owner in Output 1");
-                return Err(Error::CheckScript);
-            }
+    Ok(())
+}
 
-            Ok(())
-        }
-    } else {
-        let dob_selling = ckb_std::high_level::load_cell_lock_hash(1, Source::Output)?;
+// The xUDT and CKB not used by the intent go back to `change_script_hash`, the xUDT
+// change at `change_location`.
+fn check_change(data: &BuyIntentData) -> Result<(), Error> {
+    let change_script_hash: Hash = data.change_script_hash().into();
+    let dob_selling_script_hash: Hash = data.dob_selling_script_hash().into();
+    let asset: Asset = data.xudt_script_hash().into();
+    let change_location = {
+        let location: u32 = data.change_location().unpack();
+        location as usize
+    };
 
-        if dob_selling != data.dob_selling_script_hash().as_slice() {
-            log::error!("Dob Selling Script Hash failed");
-            return Err(Error::CheckScript);
+    let change_lock_hash = match load_cell_lock_hash(change_location, Source::Output) {
+        Ok(hash) => hash,
+        Err(SysError::IndexOutOfBound) => {
+            log::error!(
+                "Change output not found, change location: {}",
+                change_location
+            );
+            return Err(Error::TxStructure);
         }
+        Err(err) => return Err(err.into()),
+    };
+    if change_script_hash != change_lock_hash {
+        log::error!("The lock of the change output is not change_script_hash");
+        return Err(Error::CheckScript);
+    }
 
-        let udt_info = utils::UDTInfo::new(xudt_script_hash)?;
-        udt_info.check_udt()?;
+    // The CKB change is covered by the capacity check below
+    if let Asset::XUDT(_) = asset {
+        check_udt_change(data, change_location)?;
+    }
 
-        if udt_info.inputs.len() != 1 {
-            log::error!("xUDT inputs len failed");
-            return Err(Error::CheckXUDT);
+    // Capacity can only stay in the intent, the payment, or go back to the change lock
+    let intent_type_hash = load_cell_type_hash(0, Source::GroupOutput)?;
+    for (index, lock_hash) in QueryIter::new(load_cell_lock_hash, Source::Output).enumerate() {
+        if change_script_hash == lock_hash {
+            continue;
         }
-        if udt_info.outputs.len() != 2 {
-            log::error!("xUDT outputs len failed");
-            return Err(Error::CheckXUDT);
+        let type_hash = load_cell_type_hash(index, Source::Output)?;
+        if type_hash == intent_type_hash
+            || (dob_selling_script_hash == lock_hash && asset.is_held_by(&type_hash))
+        {
+            continue;
         }
+        log::error!(
+            "Capacity of Output {} is not returned to the change lock",
+            index
+        );
+        return Err(Error::CapacityError);
+    }
 
-        if udt_info.inputs[0].1 != 0 || udt_info.outputs[0].1 != 0 || udt_info.outputs[1].1 != 1 {
-            log::error!(
-                "xUDT position failed, inputs: {:?}, output: {:?}",
-                udt_info.inputs,
-                udt_info.outputs
-            );
-            return Err(Error::CheckXUDT);
-        }
+    Ok(())
+}
 
-        let asset_amount: u128 = data.asset_amount().unpack();
-        if udt_info.outputs[1].0 != asset_amount {
+fn check_udt_change(data: &BuyIntentData, change_location: usize) -> Result<(), Error> {
+    let dob_selling_script_hash: Hash = data.dob_selling_script_hash().into();
+    let udt_info = utils::UDTInfo::new(data.xudt_script_hash().into())?;
+    let asset_amount: u128 = data.asset_amount().unpack();
+    let udt_change = udt_info
+        .input_total()
+        .checked_sub(asset_amount)
+        .ok_or_else(|| {
             log::error!(
-                "Incorrect xUDT payment: Need: {}, Actually: {}",
+                "Insufficient xUDT in Input, Need: {}, Actually: {}",
                 asset_amount,
-                udt_info.outputs[0].0
+                udt_info.input_total()
+            );
+            Error::CheckXUDT
+        })?;
+    let mut change = 0;
+    for cell in &udt_info.outputs {
+        if cell.index == change_location {
+            change = cell.amount;
+        } else if cell.lock_script_hash != dob_selling_script_hash {
+            log::error!(
+                "xUDT change is not at change location, found: {}",
+                cell.index
             );
             return Err(Error::CheckXUDT);
         }
+    }
+    if change != udt_change {
+        log::error!(
+            "Incorrect xUDT change: Need: {}, Actually: {}",
+            udt_change,
+            change
+        );
+        return Err(Error::CheckXUDT);
+    }
 
-        let capacity = load_cell_capacity(2, Source::Output)?;
+    Ok(())
+}
 
-        let buy_intent_capacity =
-            u64::from_le_bytes(data.min_capacity().as_slice().try_into().map_err(|e| {
-                log::error!("Parse BuyIntentData failed, {:?}", e);
-                Error::Unknow
-            })?);
+// The intent cell must hold `min_capacity`, reserved for the spore cell minted to the buyer.
+fn check_capacity(data: &BuyIntentData) -> Result<(), Error> {
+    let capacity = load_cell_capacity(0, Source::GroupOutput)?;
+    let min_capacity: u64 = data.min_capacity().unpack();
+    if capacity < min_capacity {
+        log::error!(
+            "Capacity does not meet transaction needs, required: {}, actual: {}",
+            min_capacity,
+            capacity
+        );
+        return Err(Error::CapacityError);
+    }
+    Ok(())
+}
+
+// The DobSelling is sold, and the spore minted to the buyer fits in the `min_capacity` the
+// intent reserved for it, which the intent cell holds.
+fn check_spore_capacity(data: &BuyIntentData) -> Result<(), Error> {
+    let dob_selling_script_hash: Hash = data.dob_selling_script_hash().into();
+    let index = QueryIter::new(load_cell_lock_hash, Source::Input)
+        .position(|f| dob_selling_script_hash == f)
+        .ok_or_else(|| {
+            log::error!("DobSelling not found");
+            Error::CheckScript
+        })?;
+    let witness = load_witness_args(index, Source::Input)?
+        .lock()
+        .to_opt()
+        .ok_or_else(|| {
+            log::error!("Load witnesses of DobSelling {} failed", index);
+            Error::ParseWitness
+        })?
+        .raw_data();
+    DobSellingDataReader::verify(&witness, false)?;
+    let dob_selling_data = DobSellingData::new_unchecked(witness);
+    if Hash::ckb_hash(dob_selling_data.as_slice())
+        != load_cell_lock(index, Source::Input)?
+            .args()
+            .raw_data()
+            .as_ref()
+    {
+        log::error!("Check DobSelling data hash failed");
+        return Err(Error::VerifiedData);
+    }
+
+    let min_capacity: u64 = data.min_capacity().unpack();
+    let capacity = load_cell_capacity(0, Source::GroupInput)?;
+    if capacity < min_capacity {
+        log::error!(
+            "BuyIntent holds less than min_capacity: {}, capacity: {}",
+            min_capacity,
+            capacity
+        );
+        return Err(Error::CapacityError);
+    }
 
-        if capacity > buy_intent_capacity {
+    let spore_data_hash: Hash = dob_selling_data.spore_data_hash().into();
+    let mut minted = false;
+    for (index, data_hash) in QueryIter::new(load_cell_data_hash, Source::Output).enumerate() {
+        if spore_data_hash != data_hash {
+            continue;
+        }
+        minted = true;
+        let occupied = load_cell_occupied_capacity(index, Source::Output)?;
+        if occupied > min_capacity {
             log::error!(
-                "Capacity does not meet transaction needs, required: {}, actual: {}",
-                buy_intent_capacity,
-                capacity
+                "Spore in Output {} occupies more than min_capacity: {}, occupied: {}",
+                index,
+                min_capacity,
+                occupied
             );
             return Err(Error::CapacityError);
         }
-        Ok(())
+    }
+    if !minted {
+        log::error!("Spore of DobSelling not found in Output");
+        return Err(Error::Spore);
+    }
+    Ok(())
+}
+
+// The capacity of the BuyIntents belongs to the buyer. It pays for the spore minted to the
+// buyer, and whatever is left goes back to the buyer as well.
+fn check_refund(data: &BuyIntentData) -> Result<(), Error> {
+    let owner_script_hash: Hash = data.owner_script_hash().into();
+    let script = load_script()?;
+
+    // All BuyIntents of this buyer in the transaction
+    let mut intent_capacity = 0u64;
+    for (index, type_script) in QueryIter::new(load_cell_type, Source::Input).enumerate() {
+        let is_intent = type_script.is_some_and(|s| {
+            s.code_hash().as_slice() == script.code_hash().as_slice()
+                && s.hash_type() == script.hash_type()
+        });
+        if !is_intent {
+            continue;
+        }
+
+        let witness = load_witness_args(index, Source::Input)?
+            .input_type()
+            .to_opt()
+            .ok_or_else(|| {
+                log::error!("Load witnesses of BuyIntent {} failed", index);
+                Error::TxStructure
+            })?
+            .raw_data();
+        types::BuyIntentDataReader::verify(&witness, false)?;
+        let intent = BuyIntentData::new_unchecked(witness);
+        if owner_script_hash != intent.owner_script_hash() {
+            continue;
+        }
+
+        intent_capacity = intent_capacity
+            .checked_add(load_cell_capacity(index, Source::Input)?)
+            .ok_or(Error::CapacityError)?;
+    }
+
+    let mut refund = 0u64;
+    for (index, lock_hash) in QueryIter::new(load_cell_lock_hash, Source::Output).enumerate() {
+        if owner_script_hash == lock_hash {
+            refund = refund
+                .checked_add(load_cell_capacity(index, Source::Output)?)
+                .ok_or(Error::CapacityError)?;
+        }
+    }
+
+    if refund < intent_capacity {
+        log::error!(
+            "Capacity of BuyIntent is not returned to the buyer, intent: {}, returned: {}",
+            intent_capacity,
+            refund
+        );
+        return Err(Error::CapacityError);
+    }
+    Ok(())
+}
+
+// After expiry the intent is revoked with its DobSelling, whose asset the DobSelling returns.
+// The capacity of both goes back to the buyer.
+fn revocation(data: &BuyIntentData) -> Result<(), Error> {
+    let dob_selling_script_hash: Hash = data.dob_selling_script_hash().into();
+    let index = QueryIter::new(load_cell_lock_hash, Source::Input)
+        .position(|f| dob_selling_script_hash == f)
+        .ok_or_else(|| {
+            log::error!("Revocation failed, DobSelling not found in Input");
+            Error::CheckScript
+        })?;
+    let capacity = load_cell_capacity(0, Source::GroupInput)?
+        .checked_add(load_cell_capacity(index, Source::Input)?)
+        .ok_or(Error::CapacityError)?;
+
+    let owner_script_hash: Hash = data.owner_script_hash().into();
+    let mut refund = 0u64;
+    for (index, lock_hash) in QueryIter::new(load_cell_lock_hash, Source::Output).enumerate() {
+        if owner_script_hash == lock_hash {
+            refund = refund
+                .checked_add(load_cell_capacity(index, Source::Output)?)
+                .ok_or(Error::CapacityError)?;
+        }
+    }
+    if refund < capacity {
+        log::error!(
+            "Revocation failed, capacity of intent and DobSelling: {}, returned: {}",
+            capacity,
+            refund
+        );
+        return Err(Error::CapacityError);
+    }
+
+    Ok(())
+}
+
+fn program_entry2() -> Result<(), Error> {
+    let is_input = is_input()?;
+    let (data, accountbook_hash) = load_verified_data(is_input)?;
+
+    if is_input {
+        let ret = check_account_book(accountbook_hash, data.asset_amount().unpack());
+        if ret.is_ok() {
+            check_spore_capacity(&data)?;
+            check_refund(&data)?;
+            Ok(())
+        } else {
+            let since = Since::load_input(0, Source::GroupInput)?;
+            let expire_since: Since = data.expire_since().try_into()?;
+            if !since.reaches(&expire_since)? {
+                return ret;
+            }
+
+            revocation(&data)
+        }
+    } else {
+        let _expire_since: Since = data.expire_since().try_into()?;
+        check_payment(&data)?;
+        check_change(&data)?;
+
+        check_capacity(&data)
     }
 }
 
@@ -11,16 +11,18 @@ ckb_std::default_alloc!();
 
 use ckb_std::{
     ckb_constants::Source,
-    ckb_types::prelude::{Entity, Reader},
+    ckb_types::prelude::{Entity, Reader, Unpack},
     high_level::{
-        load_cell_data, load_cell_data_hash, load_cell_lock_hash, load_cell_type,
-        load_cell_type_hash, load_script, load_witness_args, QueryIter,
+        load_cell_capacity, load_cell_data_hash, load_cell_lock_hash, load_cell_type,
+        load_cell_type_hash, load_script, load_script_hash, load_witness_args, QueryIter,
     },
     log,
 };
 use types::error::SilentBerryError as Error;
-use types::DobSellingData;
-use utils::Hash;
+use types::{BuyIntentData, DobSellingData};
+use utils::{Asset, Hash, Since, UDTInfo};
+
+use alloc::vec::Vec;
 
 fn load_verified_data() -> Result<DobSellingData, Error> {
     let args = load_script()?.args().raw_data();
@@ -53,13 +55,9 @@ fn load_verified_data() -> Result<DobSellingData, Error> {
     Ok(data)
 }
 
-fn check_spore_data(hash: Hash) -> Result<(), Error> {
-    if QueryIter::new(load_cell_data_hash, Source::Output).all(|f| hash != f) {
-        // log::error!("Spore Error, SporeData does not match Hash");
-        Err(Error::CheckScript)
-    } else {
-        Ok(())
-    }
+// Whether the spore of this DobSelling is minted in the transaction
+fn is_sold(hash: Hash) -> bool {
+    QueryIter::new(load_cell_data_hash, Source::Output).any(|f| hash == f)
 }
 
 fn check_account_book(account_book_hash: Hash) -> Result<(), Error> {
@@ -75,45 +73,143 @@ fn check_account_book(account_book_hash: Hash) -> Result<(), Error> {
     Ok(())
 }
 
-fn check_buy_intent_code_hash(hash: Hash) -> Result<(), Error> {
-    QueryIter::new(load_cell_type, Source::Input).any(|f| {
-        if f.is_some() {
-            let h: Hash = f.unwrap().code_hash().into();
-            h == hash
-        } else {
-            false
+// The BuyIntents in Input that are for this DobSelling, with their indexes
+fn load_buy_intents(data: &DobSellingData) -> Result<Vec<(usize, BuyIntentData)>, Error> {
+    let buy_intent_code_hash: Hash = data.buy_intent_code_hash().into();
+    let dob_selling_script_hash: Hash = load_script_hash()?.into();
+
+    let mut intents = Vec::new();
+    for (index, type_script) in QueryIter::new(load_cell_type, Source::Input).enumerate() {
+        let type_script = match type_script {
+            Some(s) if buy_intent_code_hash == s.code_hash() => s,
+            _ => continue,
+        };
+        let args = type_script.args().raw_data();
+        if args.len() != utils::HASH_SIZE * 2 {
+            log::error!("BuyIntent args len is not {}", utils::HASH_SIZE * 2);
+            return Err(Error::VerifiedData);
         }
-    });
 
-    Ok(())
+        let witness = load_witness_args(index, Source::Input)?
+            .input_type()
+            .to_opt()
+            .ok_or_else(|| {
+                log::error!("Load witnesses of BuyIntent {} failed", index);
+                Error::ParseWitness
+            })?
+            .raw_data();
+        types::BuyIntentDataReader::verify(&witness, false)?;
+        let intent = BuyIntentData::new_unchecked(witness);
+        if Hash::ckb_hash(intent.as_slice()) != &args[utils::HASH_SIZE..] {
+            log::error!("Check BuyIntent data hash failed");
+            return Err(Error::VerifiedData);
+        }
+
+        if dob_selling_script_hash == intent.dob_selling_script_hash() {
+            intents.push((index, intent));
+        }
+    }
+    Ok(intents)
 }
 
-fn revocation(data: DobSellingData) -> Result<(), Error> {
-    let hash = load_cell_lock_hash(0, Source::Output)?;
-    let owner_script_hash: Hash = data.owner_script_hash().into();
-    if owner_script_hash != hash {
-        log::error!("Revocation failed, owner hash");
+// The only BuyIntent in Input that is for this DobSelling, with its index
+fn load_buy_intent(data: &DobSellingData) -> Result<(usize, BuyIntentData), Error> {
+    let mut intents = load_buy_intents(data)?;
+    if intents.len() != 1 {
+        log::error!(
+            "BuyIntent of DobSelling quantity error in Input, Need 1, Found {}",
+            intents.len()
+        );
         return Err(Error::CheckScript);
     }
+    Ok(intents.remove(0))
+}
 
-    let amount1 = u128::from_le_bytes(load_cell_data(0, Source::Input)?.try_into().unwrap());
-    let amount2 = u128::from_le_bytes(load_cell_data(0, Source::Output)?.try_into().unwrap());
-    if amount1 != amount2 {
-        log::error!("Revocation failed, input: {}, output: {}", amount1, amount2);
+fn selling(data: DobSellingData) -> Result<(), Error> {
+    check_account_book(data.account_book_script_hash().into())?;
+    let (_, intent) = load_buy_intent(&data)?;
+
+    let (asset, amount) = load_selling_udt()?;
+    if asset != intent.xudt_script_hash().into() {
+        log::error!("DobSelling does not hold the asset of the BuyIntent");
+        return Err(Error::CheckXUDT);
+    }
+    let asset_amount: u128 = intent.asset_amount().unpack();
+    if amount != asset_amount {
+        log::error!(
+            "Amount of DobSelling: {} is not the BuyIntent: {}",
+            amount,
+            asset_amount
+        );
         return Err(Error::CheckXUDT);
     }
 
-    let buy_intent_code_hash: Hash = data.buy_intent_code_hash().into();
-    let lock_code_hash: Hash = load_cell_type(1, Source::Input)?
-        .ok_or_else(|| {
-            log::error!("Input[1] type script is None");
-            Error::TxStructure
-        })?
-        .code_hash()
-        .into();
-    if buy_intent_code_hash != lock_code_hash {
-        log::error!("Revocation failed, Buy Intent not fount in Input 1");
-        return Err(Error::CheckScript);
+    // The spore is minted to the buyer
+    let spore_data_hash: Hash = data.spore_data_hash().into();
+    let owner_script_hash: Hash = intent.owner_script_hash().into();
+    for (index, data_hash) in QueryIter::new(load_cell_data_hash, Source::Output).enumerate() {
+        if spore_data_hash == data_hash
+            && owner_script_hash != load_cell_lock_hash(index, Source::Output)?
+        {
+            log::error!("Spore in Output {} is not minted to the buyer", index);
+            return Err(Error::Spore);
+        }
+    }
+
+    Ok(())
+}
+
+// The asset and amount held by this DobSelling, CKB if it has no type script
+fn load_selling_udt() -> Result<(Asset, u128), Error> {
+    if load_cell_capacity(1, Source::GroupInput).is_ok() {
+        log::error!("There can be only one GroupInput");
+        return Err(Error::TxStructure);
+    }
+    let asset = match load_cell_type_hash(0, Source::GroupInput)? {
+        Some(type_hash) => Asset::XUDT(type_hash.into()),
+        None => Asset::CKB,
+    };
+    let amount = asset
+        .load_amount(0, Source::GroupInput)?
+        .ok_or(Error::CheckXUDT)?;
+    Ok((asset, amount))
+}
+
+// The asset is paid by the buyer, it only goes back to the buyer once the BuyIntent expires.
+fn revocation(data: DobSellingData) -> Result<(), Error> {
+    let (asset, amount) = load_selling_udt()?;
+    let (index, intent) = load_buy_intent(&data)?;
+    let since = Since::load_input(index, Source::Input)?;
+    let expire_since: Since = intent.expire_since().try_into()?;
+    if !since.reaches(&expire_since)? {
+        log::error!(
+            "Revocation failed, BuyIntent is not expired, since: {:#x}, expire: {:#x}",
+            since.as_u64(),
+            expire_since.as_u64()
+        );
+        return Err(Error::ExpireSince);
+    }
+    let owner_script_hash: Hash = intent.owner_script_hash().into();
+
+    let udt_info = UDTInfo::new(asset)?;
+    udt_info.check_udt()?;
+    // Anyone may submit the revocation, the buyer only pays the fee out of the refund when it
+    // signs the transaction
+    let fee = if QueryIter::new(load_cell_lock_hash, Source::Input).any(|h| owner_script_hash == h)
+    {
+        udt_info.asset.max_fee()
+    } else {
+        0
+    };
+    let flow = udt_info.flow(&owner_script_hash);
+    if !flow.received(amount, fee) {
+        log::error!(
+            "Revocation failed, DobSelling: {}, owner input: {}, output: {}",
+            amount,
+            flow.input,
+            flow.output
+        );
+        return Err(Error::CheckXUDT);
     }
 
     Ok(())
@@ -121,14 +217,11 @@ fn revocation(data: DobSellingData) -> Result<(), Error> {
 
 fn program_entry2() -> Result<(), Error> {
     let data = load_verified_data()?;
-    let ret = check_spore_data(data.spore_data_hash().into());
-    if ret.is_err() && ret.unwrap_err() == Error::CheckScript {
-        revocation(data)?;
+    if is_sold(data.spore_data_hash().into()) {
+        selling(data)
     } else {
-        check_account_book(data.account_book_script_hash().into())?;
-        check_buy_intent_code_hash(data.buy_intent_code_hash().into())?;
+        revocation(data)
     }
-    Ok(())
 }
 
 pub fn program_entry() -> i8 {
@@ -11,18 +11,18 @@ ckb_std::default_alloc!();
 
 use ckb_std::{
     ckb_constants::Source,
-    ckb_types::prelude::{Entity, Reader},
+    ckb_types::prelude::{Entity, Reader, Unpack},
     error::SysError,
     high_level::{
-        load_cell_capacity, load_cell_data, load_cell_type, load_cell_type_hash, load_script,
-        load_witness_args, QueryIter,
+        load_cell_capacity, load_cell_data, load_cell_lock_hash, load_cell_type,
+        load_cell_type_hash, load_script, load_witness_args, QueryIter,
     },
     log::{self},
 };
 use spore_types::spore::{SporeData, SporeDataReader};
 use types::error::SilentBerryError as Error;
 use types::WithdrawalIntentData;
-use utils::{Hash, Level, UDTInfo};
+use utils::{Hash, Level, Since, UDTInfo};
 
 fn is_input() -> Result<bool, Error> {
     let input = match load_cell_capacity(0, Source::GroupInput) {
@@ -117,6 +117,21 @@ fn check_spore(data: &WithdrawalIntentData) -> Result<(), Error> {
         SporeData::new_unchecked(spore_data1.into())
     };
 
+    // Only the holder of the spore can create the intent, and keeps the spore
+    let owner_script_hash: Hash = data.owner_script_hash().into();
+    if owner_script_hash != load_cell_lock_hash(0, Source::Input)? {
+        log::error!("The lock of the input spore is not owner_script_hash");
+        return Err(Error::Spore);
+    }
+    if owner_script_hash != load_cell_lock_hash(0, Source::Output)? {
+        log::error!("The lock of the output spore is not owner_script_hash");
+        return Err(Error::Spore);
+    }
+    if load_cell_type_hash(0, Source::Input)? != load_cell_type_hash(0, Source::Output)? {
+        log::error!("The type of the output spore is not the input spore");
+        return Err(Error::Spore);
+    }
+
     let cluster_id: Hash = spore_data.cluster_id().try_into()?;
     let cluster_id2: Hash = data.cluster_id().into();
     if cluster_id != cluster_id2 {
@@ -124,25 +139,29 @@ fn check_spore(data: &WithdrawalIntentData) -> Result<(), Error> {
         return Err(Error::Spore);
     }
 
-    let spore_id: Hash = load_cell_type(0, Source::Input)?
-        .ok_or_else(|| {
-            log::error!("Load Cell type scripe failed, Type is None");
-            Error::Spore
-        })?
-        .args()
-        .try_into()?;
+    let spore_type = load_cell_type(0, Source::Input)?.ok_or_else(|| {
+        log::error!("Load Cell type scripe failed, Type is None");
+        Error::Spore
+    })?;
+    let spore_code_hash: Hash = data.spore_code_hash().into();
+    let spore_hash_type: u8 = data.spore_hash_type().into();
+    if spore_code_hash != spore_type.code_hash()
+        || spore_hash_type != u8::from(spore_type.hash_type())
+    {
+        log::error!("The type of the input spore is not the Spore passed in");
+        return Err(Error::Spore);
+    }
+
+    let spore_id: Hash = spore_type.args().try_into()?;
     let spore_id2: Hash = data.spore_id().into();
     if spore_id != spore_id2 {
         log::error!("The spore id in Spore is different from the one passed in");
         return Err(Error::Spore);
     }
 
-    let spore_level: Level = spore_data.try_into()?;
-    let spore_level_by_data: Level = data.spore_level().try_into()?;
-    if spore_level_by_data != spore_level {
-        log::error!("Check spore level failed");
-        return Err(Error::Spore);
-    }
+    // The DNA layout is configured in the AccountBook, which settles by the level recorded
+    // in its SMT when the spore was sold. Here it only has to be a member level (A~D).
+    let _spore_level: Level = data.spore_level().try_into()?;
 
     Ok(())
 }
@@ -160,18 +179,68 @@ fn check_account_book(hash: Hash) -> Result<(), Error> {
     Ok(())
 }
 
+// The capacity of the intent goes back to the owner at `change_location`
+fn revocation(data: &WithdrawalIntentData) -> Result<(), Error> {
+    let change_location = {
+        let location: u32 = data.change_location().unpack();
+        location as usize
+    };
+
+    let owner_script_hash: Hash = data.owner_script_hash().into();
+    let lock_script_hash = match load_cell_lock_hash(change_location, Source::Output) {
+        Ok(hash) => hash,
+        Err(SysError::IndexOutOfBound) => {
+            log::error!(
+                "Revocation failed, output not found, change location: {}",
+                change_location
+            );
+            return Err(Error::TxStructure);
+        }
+        Err(err) => return Err(err.into()),
+    };
+    if owner_script_hash != lock_script_hash {
+        log::error!(
+            "Revocation failed, not found owner in Output {}",
+            change_location
+        );
+        return Err(Error::CheckScript);
+    }
+
+    let intent_capacity = load_cell_capacity(0, Source::GroupInput)?;
+    let capacity = load_cell_capacity(change_location, Source::Output)?;
+    if capacity < intent_capacity {
+        log::error!(
+            "Revocation failed, capacity of intent: {}, returned: {}",
+            intent_capacity,
+            capacity
+        );
+        return Err(Error::CapacityError);
+    }
+
+    Ok(())
+}
+
 fn program_entry2() -> Result<(), Error> {
     let is_input = is_input()?;
     let (data, accountbook_hash) = load_verified_data(is_input)?;
 
     if is_input {
-        check_account_book(accountbook_hash)?;
+        let ret = check_account_book(accountbook_hash);
+        if ret.is_ok() {
+            let udt_info = UDTInfo::new(data.xudt_script_hash().into())?;
+            udt_info.check_udt()?;
+            Ok(())
+        } else {
+            let since = Since::load_input(0, Source::GroupInput)?;
+            let expire_since: Since = data.expire_since().try_into()?;
+            if !since.reaches(&expire_since)? {
+                return ret;
+            }
 
-        let xudt_script_hash: Hash = data.xudt_script_hash().into();
-        let udt_info = UDTInfo::new(xudt_script_hash)?;
-        udt_info.check_udt()?;
-        Ok(())
+            revocation(&data)
+        }
     } else {
+        let _expire_since: Since = data.expire_since().try_into()?;
         // check spore
         check_spore(&data)?;
         Ok(())
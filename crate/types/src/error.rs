@@ -23,6 +23,10 @@ pub enum SilentBerryError {
     CapacityError,
     ExpireSince,
     AccountBookModified,
+    LevelSoldOut,
+    Paused,
+    SaleWindow,
+    Allowlist,
 }
 
 impl From<ckb_std::error::SysError> for SilentBerryError {
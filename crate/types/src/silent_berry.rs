@@ -472,6 +472,8 @@ impl ::core::fmt::Display for WithdrawalIntentData {
         write!(f, "{}: {}", "xudt_script_hash", self.xudt_script_hash())?;
         write!(f, ", {}: {}", "spore_level", self.spore_level())?;
         write!(f, ", {}: {}", "spore_id", self.spore_id())?;
+        write!(f, ", {}: {}", "spore_code_hash", self.spore_code_hash())?;
+        write!(f, ", {}: {}", "spore_hash_type", self.spore_hash_type())?;
         write!(f, ", {}: {}", "cluster_id", self.cluster_id())?;
         write!(f, ", {}: {}", "expire_since", self.expire_since())?;
         write!(f, ", {}: {}", "owner_script_hash", self.owner_script_hash())?;
@@ -490,15 +492,17 @@ impl ::core::default::Default for WithdrawalIntentData {
     }
 }
 impl WithdrawalIntentData {
-    const DEFAULT_VALUE: [u8; 173] = [
-        173, 0, 0, 0, 32, 0, 0, 0, 64, 0, 0, 0, 65, 0, 0, 0, 97, 0, 0, 0, 129, 0, 0, 0, 137, 0, 0,
-        0, 169, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    const DEFAULT_VALUE: [u8; 214] = [
+        214, 0, 0, 0, 40, 0, 0, 0, 72, 0, 0, 0, 73, 0, 0, 0, 105, 0, 0, 0, 137, 0, 0, 0, 138, 0, 0,
+        0, 170, 0, 0, 0, 178, 0, 0, 0, 210, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0,
     ];
-    pub const FIELD_COUNT: usize = 7;
+    pub const FIELD_COUNT: usize = 9;
     pub fn total_size(&self) -> usize {
         molecule::unpack_number(self.as_slice()) as usize
     }
@@ -533,29 +537,41 @@ impl WithdrawalIntentData {
         let end = molecule::unpack_number(&slice[16..]) as usize;
         Byte32::new_unchecked(self.0.slice(start..end))
     }
-    pub fn cluster_id(&self) -> Byte32 {
+    pub fn spore_code_hash(&self) -> Byte32 {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[16..]) as usize;
         let end = molecule::unpack_number(&slice[20..]) as usize;
         Byte32::new_unchecked(self.0.slice(start..end))
     }
-    pub fn expire_since(&self) -> Uint64 {
+    pub fn spore_hash_type(&self) -> Byte {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[20..]) as usize;
         let end = molecule::unpack_number(&slice[24..]) as usize;
-        Uint64::new_unchecked(self.0.slice(start..end))
+        Byte::new_unchecked(self.0.slice(start..end))
     }
-    pub fn owner_script_hash(&self) -> Byte32 {
+    pub fn cluster_id(&self) -> Byte32 {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[24..]) as usize;
         let end = molecule::unpack_number(&slice[28..]) as usize;
         Byte32::new_unchecked(self.0.slice(start..end))
     }
-    pub fn change_location(&self) -> Uint32 {
+    pub fn expire_since(&self) -> Uint64 {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[28..]) as usize;
+        let end = molecule::unpack_number(&slice[32..]) as usize;
+        Uint64::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn owner_script_hash(&self) -> Byte32 {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[32..]) as usize;
+        let end = molecule::unpack_number(&slice[36..]) as usize;
+        Byte32::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn change_location(&self) -> Uint32 {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[36..]) as usize;
         if self.has_extra_fields() {
-            let end = molecule::unpack_number(&slice[32..]) as usize;
+            let end = molecule::unpack_number(&slice[40..]) as usize;
             Uint32::new_unchecked(self.0.slice(start..end))
         } else {
             Uint32::new_unchecked(self.0.slice(start..))
@@ -591,6 +607,8 @@ impl molecule::prelude::Entity for WithdrawalIntentData {
             .xudt_script_hash(self.xudt_script_hash())
             .spore_level(self.spore_level())
             .spore_id(self.spore_id())
+            .spore_code_hash(self.spore_code_hash())
+            .spore_hash_type(self.spore_hash_type())
             .cluster_id(self.cluster_id())
             .expire_since(self.expire_since())
             .owner_script_hash(self.owner_script_hash())
@@ -619,6 +637,8 @@ impl<'r> ::core::fmt::Display for WithdrawalIntentDataReader<'r> {
         write!(f, "{}: {}", "xudt_script_hash", self.xudt_script_hash())?;
         write!(f, ", {}: {}", "spore_level", self.spore_level())?;
         write!(f, ", {}: {}", "spore_id", self.spore_id())?;
+        write!(f, ", {}: {}", "spore_code_hash", self.spore_code_hash())?;
+        write!(f, ", {}: {}", "spore_hash_type", self.spore_hash_type())?;
         write!(f, ", {}: {}", "cluster_id", self.cluster_id())?;
         write!(f, ", {}: {}", "expire_since", self.expire_since())?;
         write!(f, ", {}: {}", "owner_script_hash", self.owner_script_hash())?;
@@ -631,7 +651,7 @@ impl<'r> ::core::fmt::Display for WithdrawalIntentDataReader<'r> {
     }
 }
 impl<'r> WithdrawalIntentDataReader<'r> {
-    pub const FIELD_COUNT: usize = 7;
+    pub const FIELD_COUNT: usize = 9;
     pub fn total_size(&self) -> usize {
         molecule::unpack_number(self.as_slice()) as usize
     }
@@ -666,29 +686,41 @@ impl<'r> WithdrawalIntentDataReader<'r> {
         let end = molecule::unpack_number(&slice[16..]) as usize;
         Byte32Reader::new_unchecked(&self.as_slice()[start..end])
     }
-    pub fn cluster_id(&self) -> Byte32Reader<'r> {
+    pub fn spore_code_hash(&self) -> Byte32Reader<'r> {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[16..]) as usize;
         let end = molecule::unpack_number(&slice[20..]) as usize;
         Byte32Reader::new_unchecked(&self.as_slice()[start..end])
     }
-    pub fn expire_since(&self) -> Uint64Reader<'r> {
+    pub fn spore_hash_type(&self) -> ByteReader<'r> {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[20..]) as usize;
         let end = molecule::unpack_number(&slice[24..]) as usize;
-        Uint64Reader::new_unchecked(&self.as_slice()[start..end])
+        ByteReader::new_unchecked(&self.as_slice()[start..end])
     }
-    pub fn owner_script_hash(&self) -> Byte32Reader<'r> {
+    pub fn cluster_id(&self) -> Byte32Reader<'r> {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[24..]) as usize;
         let end = molecule::unpack_number(&slice[28..]) as usize;
         Byte32Reader::new_unchecked(&self.as_slice()[start..end])
     }
-    pub fn change_location(&self) -> Uint32Reader<'r> {
+    pub fn expire_since(&self) -> Uint64Reader<'r> {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[28..]) as usize;
+        let end = molecule::unpack_number(&slice[32..]) as usize;
+        Uint64Reader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn owner_script_hash(&self) -> Byte32Reader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[32..]) as usize;
+        let end = molecule::unpack_number(&slice[36..]) as usize;
+        Byte32Reader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn change_location(&self) -> Uint32Reader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[36..]) as usize;
         if self.has_extra_fields() {
-            let end = molecule::unpack_number(&slice[32..]) as usize;
+            let end = molecule::unpack_number(&slice[40..]) as usize;
             Uint32Reader::new_unchecked(&self.as_slice()[start..end])
         } else {
             Uint32Reader::new_unchecked(&self.as_slice()[start..])
@@ -745,9 +777,11 @@ impl<'r> molecule::prelude::Reader<'r> for WithdrawalIntentDataReader<'r> {
         ByteReader::verify(&slice[offsets[1]..offsets[2]], compatible)?;
         Byte32Reader::verify(&slice[offsets[2]..offsets[3]], compatible)?;
         Byte32Reader::verify(&slice[offsets[3]..offsets[4]], compatible)?;
-        Uint64Reader::verify(&slice[offsets[4]..offsets[5]], compatible)?;
+        ByteReader::verify(&slice[offsets[4]..offsets[5]], compatible)?;
         Byte32Reader::verify(&slice[offsets[5]..offsets[6]], compatible)?;
-        Uint32Reader::verify(&slice[offsets[6]..offsets[7]], compatible)?;
+        Uint64Reader::verify(&slice[offsets[6]..offsets[7]], compatible)?;
+        Byte32Reader::verify(&slice[offsets[7]..offsets[8]], compatible)?;
+        Uint32Reader::verify(&slice[offsets[8]..offsets[9]], compatible)?;
         Ok(())
     }
 }
@@ -756,13 +790,15 @@ pub struct WithdrawalIntentDataBuilder {
     pub(crate) xudt_script_hash: Byte32,
     pub(crate) spore_level: Byte,
     pub(crate) spore_id: Byte32,
+    pub(crate) spore_code_hash: Byte32,
+    pub(crate) spore_hash_type: Byte,
     pub(crate) cluster_id: Byte32,
     pub(crate) expire_since: Uint64,
     pub(crate) owner_script_hash: Byte32,
     pub(crate) change_location: Uint32,
 }
 impl WithdrawalIntentDataBuilder {
-    pub const FIELD_COUNT: usize = 7;
+    pub const FIELD_COUNT: usize = 9;
     pub fn xudt_script_hash(mut self, v: Byte32) -> Self {
         self.xudt_script_hash = v;
         self
@@ -775,6 +811,14 @@ impl WithdrawalIntentDataBuilder {
         self.spore_id = v;
         self
     }
+    pub fn spore_code_hash(mut self, v: Byte32) -> Self {
+        self.spore_code_hash = v;
+        self
+    }
+    pub fn spore_hash_type(mut self, v: Byte) -> Self {
+        self.spore_hash_type = v;
+        self
+    }
     pub fn cluster_id(mut self, v: Byte32) -> Self {
         self.cluster_id = v;
         self
@@ -800,6 +844,8 @@ impl molecule::prelude::Builder for WithdrawalIntentDataBuilder {
             + self.xudt_script_hash.as_slice().len()
             + self.spore_level.as_slice().len()
             + self.spore_id.as_slice().len()
+            + self.spore_code_hash.as_slice().len()
+            + self.spore_hash_type.as_slice().len()
             + self.cluster_id.as_slice().len()
             + self.expire_since.as_slice().len()
             + self.owner_script_hash.as_slice().len()
@@ -815,6 +861,10 @@ impl molecule::prelude::Builder for WithdrawalIntentDataBuilder {
         offsets.push(total_size);
         total_size += self.spore_id.as_slice().len();
         offsets.push(total_size);
+        total_size += self.spore_code_hash.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.spore_hash_type.as_slice().len();
+        offsets.push(total_size);
         total_size += self.cluster_id.as_slice().len();
         offsets.push(total_size);
         total_size += self.expire_since.as_slice().len();
@@ -829,6 +879,8 @@ impl molecule::prelude::Builder for WithdrawalIntentDataBuilder {
         writer.write_all(self.xudt_script_hash.as_slice())?;
         writer.write_all(self.spore_level.as_slice())?;
         writer.write_all(self.spore_id.as_slice())?;
+        writer.write_all(self.spore_code_hash.as_slice())?;
+        writer.write_all(self.spore_hash_type.as_slice())?;
         writer.write_all(self.cluster_id.as_slice())?;
         writer.write_all(self.expire_since.as_slice())?;
         writer.write_all(self.owner_script_hash.as_slice())?;
@@ -2211,7 +2263,12 @@ impl ::core::fmt::Display for AccountBookData {
             "input_type_proxy_lock_code_hash",
             self.input_type_proxy_lock_code_hash()
         )?;
+        write!(f, ", {}: {}", "spore_code_hash", self.spore_code_hash())?;
+        write!(f, ", {}: {}", "spore_hash_type", self.spore_hash_type())?;
+        write!(f, ", {}: {}", "cluster_code_hash", self.cluster_code_hash())?;
         write!(f, ", {}: {}", "cluster_id", self.cluster_id())?;
+        write!(f, ", {}: {}", "level_offset", self.level_offset())?;
+        write!(f, ", {}: {}", "level_width", self.level_width())?;
         write!(f, ", {}: {}", "proof", self.proof())?;
         write!(f, ", {}: {}", "total_a", self.total_a())?;
         write!(f, ", {}: {}", "total_b", self.total_b())?;
@@ -2231,9 +2288,11 @@ impl ::core::default::Default for AccountBookData {
     }
 }
 impl AccountBookData {
-    const DEFAULT_VALUE: [u8; 308] = [
-        52, 1, 0, 0, 48, 0, 0, 0, 80, 0, 0, 0, 112, 0, 0, 0, 144, 0, 0, 0, 176, 0, 0, 0, 208, 0, 0,
-        0, 240, 0, 0, 0, 244, 0, 0, 0, 4, 1, 0, 0, 20, 1, 0, 0, 36, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    const DEFAULT_VALUE: [u8; 395] = [
+        139, 1, 0, 0, 68, 0, 0, 0, 100, 0, 0, 0, 132, 0, 0, 0, 164, 0, 0, 0, 196, 0, 0, 0, 228, 0,
+        0, 0, 4, 1, 0, 0, 5, 1, 0, 0, 37, 1, 0, 0, 69, 1, 0, 0, 70, 1, 0, 0, 71, 1, 0, 0, 75, 1, 0,
+        0, 91, 1, 0, 0, 107, 1, 0, 0, 123, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
@@ -2242,9 +2301,10 @@ impl AccountBookData {
         0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
     ];
-    pub const FIELD_COUNT: usize = 11;
+    pub const FIELD_COUNT: usize = 16;
     pub fn total_size(&self) -> usize {
         molecule::unpack_number(self.as_slice()) as usize
     }
@@ -2291,41 +2351,71 @@ impl AccountBookData {
         let end = molecule::unpack_number(&slice[24..]) as usize;
         Byte32::new_unchecked(self.0.slice(start..end))
     }
-    pub fn cluster_id(&self) -> Byte32 {
+    pub fn spore_code_hash(&self) -> Byte32 {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[24..]) as usize;
         let end = molecule::unpack_number(&slice[28..]) as usize;
         Byte32::new_unchecked(self.0.slice(start..end))
     }
-    pub fn proof(&self) -> Bytes {
+    pub fn spore_hash_type(&self) -> Byte {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[28..]) as usize;
         let end = molecule::unpack_number(&slice[32..]) as usize;
-        Bytes::new_unchecked(self.0.slice(start..end))
+        Byte::new_unchecked(self.0.slice(start..end))
     }
-    pub fn total_a(&self) -> Uint128 {
+    pub fn cluster_code_hash(&self) -> Byte32 {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[32..]) as usize;
         let end = molecule::unpack_number(&slice[36..]) as usize;
-        Uint128::new_unchecked(self.0.slice(start..end))
+        Byte32::new_unchecked(self.0.slice(start..end))
     }
-    pub fn total_b(&self) -> Uint128 {
+    pub fn cluster_id(&self) -> Byte32 {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[36..]) as usize;
         let end = molecule::unpack_number(&slice[40..]) as usize;
-        Uint128::new_unchecked(self.0.slice(start..end))
+        Byte32::new_unchecked(self.0.slice(start..end))
     }
-    pub fn total_c(&self) -> Uint128 {
+    pub fn level_offset(&self) -> Byte {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[40..]) as usize;
         let end = molecule::unpack_number(&slice[44..]) as usize;
+        Byte::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn level_width(&self) -> Byte {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[44..]) as usize;
+        let end = molecule::unpack_number(&slice[48..]) as usize;
+        Byte::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn proof(&self) -> Bytes {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[48..]) as usize;
+        let end = molecule::unpack_number(&slice[52..]) as usize;
+        Bytes::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn total_a(&self) -> Uint128 {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[52..]) as usize;
+        let end = molecule::unpack_number(&slice[56..]) as usize;
+        Uint128::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn total_b(&self) -> Uint128 {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[56..]) as usize;
+        let end = molecule::unpack_number(&slice[60..]) as usize;
+        Uint128::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn total_c(&self) -> Uint128 {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[60..]) as usize;
+        let end = molecule::unpack_number(&slice[64..]) as usize;
         Uint128::new_unchecked(self.0.slice(start..end))
     }
     pub fn total_d(&self) -> Uint128 {
         let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[44..]) as usize;
+        let start = molecule::unpack_number(&slice[64..]) as usize;
         if self.has_extra_fields() {
-            let end = molecule::unpack_number(&slice[48..]) as usize;
+            let end = molecule::unpack_number(&slice[68..]) as usize;
             Uint128::new_unchecked(self.0.slice(start..end))
         } else {
             Uint128::new_unchecked(self.0.slice(start..))
@@ -2363,7 +2453,12 @@ impl molecule::prelude::Entity for AccountBookData {
             .withdrawal_intent_code_hash(self.withdrawal_intent_code_hash())
             .xudt_script_hash(self.xudt_script_hash())
             .input_type_proxy_lock_code_hash(self.input_type_proxy_lock_code_hash())
+            .spore_code_hash(self.spore_code_hash())
+            .spore_hash_type(self.spore_hash_type())
+            .cluster_code_hash(self.cluster_code_hash())
             .cluster_id(self.cluster_id())
+            .level_offset(self.level_offset())
+            .level_width(self.level_width())
             .proof(self.proof())
             .total_a(self.total_a())
             .total_b(self.total_b())
@@ -2415,7 +2510,12 @@ impl<'r> ::core::fmt::Display for AccountBookDataReader<'r> {
             "input_type_proxy_lock_code_hash",
             self.input_type_proxy_lock_code_hash()
         )?;
+        write!(f, ", {}: {}", "spore_code_hash", self.spore_code_hash())?;
+        write!(f, ", {}: {}", "spore_hash_type", self.spore_hash_type())?;
+        write!(f, ", {}: {}", "cluster_code_hash", self.cluster_code_hash())?;
         write!(f, ", {}: {}", "cluster_id", self.cluster_id())?;
+        write!(f, ", {}: {}", "level_offset", self.level_offset())?;
+        write!(f, ", {}: {}", "level_width", self.level_width())?;
         write!(f, ", {}: {}", "proof", self.proof())?;
         write!(f, ", {}: {}", "total_a", self.total_a())?;
         write!(f, ", {}: {}", "total_b", self.total_b())?;
@@ -2429,7 +2529,7 @@ impl<'r> ::core::fmt::Display for AccountBookDataReader<'r> {
     }
 }
 impl<'r> AccountBookDataReader<'r> {
-    pub const FIELD_COUNT: usize = 11;
+    pub const FIELD_COUNT: usize = 16;
     pub fn total_size(&self) -> usize {
         molecule::unpack_number(self.as_slice()) as usize
     }
@@ -2476,41 +2576,71 @@ impl<'r> AccountBookDataReader<'r> {
         let end = molecule::unpack_number(&slice[24..]) as usize;
         Byte32Reader::new_unchecked(&self.as_slice()[start..end])
     }
-    pub fn cluster_id(&self) -> Byte32Reader<'r> {
+    pub fn spore_code_hash(&self) -> Byte32Reader<'r> {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[24..]) as usize;
         let end = molecule::unpack_number(&slice[28..]) as usize;
         Byte32Reader::new_unchecked(&self.as_slice()[start..end])
     }
-    pub fn proof(&self) -> BytesReader<'r> {
+    pub fn spore_hash_type(&self) -> ByteReader<'r> {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[28..]) as usize;
         let end = molecule::unpack_number(&slice[32..]) as usize;
-        BytesReader::new_unchecked(&self.as_slice()[start..end])
+        ByteReader::new_unchecked(&self.as_slice()[start..end])
     }
-    pub fn total_a(&self) -> Uint128Reader<'r> {
+    pub fn cluster_code_hash(&self) -> Byte32Reader<'r> {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[32..]) as usize;
         let end = molecule::unpack_number(&slice[36..]) as usize;
-        Uint128Reader::new_unchecked(&self.as_slice()[start..end])
+        Byte32Reader::new_unchecked(&self.as_slice()[start..end])
     }
-    pub fn total_b(&self) -> Uint128Reader<'r> {
+    pub fn cluster_id(&self) -> Byte32Reader<'r> {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[36..]) as usize;
         let end = molecule::unpack_number(&slice[40..]) as usize;
-        Uint128Reader::new_unchecked(&self.as_slice()[start..end])
+        Byte32Reader::new_unchecked(&self.as_slice()[start..end])
     }
-    pub fn total_c(&self) -> Uint128Reader<'r> {
+    pub fn level_offset(&self) -> ByteReader<'r> {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[40..]) as usize;
         let end = molecule::unpack_number(&slice[44..]) as usize;
+        ByteReader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn level_width(&self) -> ByteReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[44..]) as usize;
+        let end = molecule::unpack_number(&slice[48..]) as usize;
+        ByteReader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn proof(&self) -> BytesReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[48..]) as usize;
+        let end = molecule::unpack_number(&slice[52..]) as usize;
+        BytesReader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn total_a(&self) -> Uint128Reader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[52..]) as usize;
+        let end = molecule::unpack_number(&slice[56..]) as usize;
+        Uint128Reader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn total_b(&self) -> Uint128Reader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[56..]) as usize;
+        let end = molecule::unpack_number(&slice[60..]) as usize;
+        Uint128Reader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn total_c(&self) -> Uint128Reader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[60..]) as usize;
+        let end = molecule::unpack_number(&slice[64..]) as usize;
         Uint128Reader::new_unchecked(&self.as_slice()[start..end])
     }
     pub fn total_d(&self) -> Uint128Reader<'r> {
         let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[44..]) as usize;
+        let start = molecule::unpack_number(&slice[64..]) as usize;
         if self.has_extra_fields() {
-            let end = molecule::unpack_number(&slice[48..]) as usize;
+            let end = molecule::unpack_number(&slice[68..]) as usize;
             Uint128Reader::new_unchecked(&self.as_slice()[start..end])
         } else {
             Uint128Reader::new_unchecked(&self.as_slice()[start..])
@@ -2569,11 +2699,16 @@ impl<'r> molecule::prelude::Reader<'r> for AccountBookDataReader<'r> {
         Byte32Reader::verify(&slice[offsets[3]..offsets[4]], compatible)?;
         Byte32Reader::verify(&slice[offsets[4]..offsets[5]], compatible)?;
         Byte32Reader::verify(&slice[offsets[5]..offsets[6]], compatible)?;
-        BytesReader::verify(&slice[offsets[6]..offsets[7]], compatible)?;
-        Uint128Reader::verify(&slice[offsets[7]..offsets[8]], compatible)?;
-        Uint128Reader::verify(&slice[offsets[8]..offsets[9]], compatible)?;
-        Uint128Reader::verify(&slice[offsets[9]..offsets[10]], compatible)?;
-        Uint128Reader::verify(&slice[offsets[10]..offsets[11]], compatible)?;
+        ByteReader::verify(&slice[offsets[6]..offsets[7]], compatible)?;
+        Byte32Reader::verify(&slice[offsets[7]..offsets[8]], compatible)?;
+        Byte32Reader::verify(&slice[offsets[8]..offsets[9]], compatible)?;
+        ByteReader::verify(&slice[offsets[9]..offsets[10]], compatible)?;
+        ByteReader::verify(&slice[offsets[10]..offsets[11]], compatible)?;
+        BytesReader::verify(&slice[offsets[11]..offsets[12]], compatible)?;
+        Uint128Reader::verify(&slice[offsets[12]..offsets[13]], compatible)?;
+        Uint128Reader::verify(&slice[offsets[13]..offsets[14]], compatible)?;
+        Uint128Reader::verify(&slice[offsets[14]..offsets[15]], compatible)?;
+        Uint128Reader::verify(&slice[offsets[15]..offsets[16]], compatible)?;
         Ok(())
     }
 }
@@ -2584,7 +2719,12 @@ pub struct AccountBookDataBuilder {
     pub(crate) withdrawal_intent_code_hash: Byte32,
     pub(crate) xudt_script_hash: Byte32,
     pub(crate) input_type_proxy_lock_code_hash: Byte32,
+    pub(crate) spore_code_hash: Byte32,
+    pub(crate) spore_hash_type: Byte,
+    pub(crate) cluster_code_hash: Byte32,
     pub(crate) cluster_id: Byte32,
+    pub(crate) level_offset: Byte,
+    pub(crate) level_width: Byte,
     pub(crate) proof: Bytes,
     pub(crate) total_a: Uint128,
     pub(crate) total_b: Uint128,
@@ -2592,7 +2732,7 @@ pub struct AccountBookDataBuilder {
     pub(crate) total_d: Uint128,
 }
 impl AccountBookDataBuilder {
-    pub const FIELD_COUNT: usize = 11;
+    pub const FIELD_COUNT: usize = 16;
     pub fn dob_selling_code_hash(mut self, v: Byte32) -> Self {
         self.dob_selling_code_hash = v;
         self
@@ -2613,10 +2753,30 @@ impl AccountBookDataBuilder {
         self.input_type_proxy_lock_code_hash = v;
         self
     }
+    pub fn spore_code_hash(mut self, v: Byte32) -> Self {
+        self.spore_code_hash = v;
+        self
+    }
+    pub fn spore_hash_type(mut self, v: Byte) -> Self {
+        self.spore_hash_type = v;
+        self
+    }
+    pub fn cluster_code_hash(mut self, v: Byte32) -> Self {
+        self.cluster_code_hash = v;
+        self
+    }
     pub fn cluster_id(mut self, v: Byte32) -> Self {
         self.cluster_id = v;
         self
     }
+    pub fn level_offset(mut self, v: Byte) -> Self {
+        self.level_offset = v;
+        self
+    }
+    pub fn level_width(mut self, v: Byte) -> Self {
+        self.level_width = v;
+        self
+    }
     pub fn proof(mut self, v: Bytes) -> Self {
         self.proof = v;
         self
@@ -2648,7 +2808,12 @@ impl molecule::prelude::Builder for AccountBookDataBuilder {
             + self.withdrawal_intent_code_hash.as_slice().len()
             + self.xudt_script_hash.as_slice().len()
             + self.input_type_proxy_lock_code_hash.as_slice().len()
+            + self.spore_code_hash.as_slice().len()
+            + self.spore_hash_type.as_slice().len()
+            + self.cluster_code_hash.as_slice().len()
             + self.cluster_id.as_slice().len()
+            + self.level_offset.as_slice().len()
+            + self.level_width.as_slice().len()
             + self.proof.as_slice().len()
             + self.total_a.as_slice().len()
             + self.total_b.as_slice().len()
@@ -2669,8 +2834,18 @@ impl molecule::prelude::Builder for AccountBookDataBuilder {
         offsets.push(total_size);
         total_size += self.input_type_proxy_lock_code_hash.as_slice().len();
         offsets.push(total_size);
+        total_size += self.spore_code_hash.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.spore_hash_type.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.cluster_code_hash.as_slice().len();
+        offsets.push(total_size);
         total_size += self.cluster_id.as_slice().len();
         offsets.push(total_size);
+        total_size += self.level_offset.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.level_width.as_slice().len();
+        offsets.push(total_size);
         total_size += self.proof.as_slice().len();
         offsets.push(total_size);
         total_size += self.total_a.as_slice().len();
@@ -2689,7 +2864,12 @@ impl molecule::prelude::Builder for AccountBookDataBuilder {
         writer.write_all(self.withdrawal_intent_code_hash.as_slice())?;
         writer.write_all(self.xudt_script_hash.as_slice())?;
         writer.write_all(self.input_type_proxy_lock_code_hash.as_slice())?;
+        writer.write_all(self.spore_code_hash.as_slice())?;
+        writer.write_all(self.spore_hash_type.as_slice())?;
+        writer.write_all(self.cluster_code_hash.as_slice())?;
         writer.write_all(self.cluster_id.as_slice())?;
+        writer.write_all(self.level_offset.as_slice())?;
+        writer.write_all(self.level_width.as_slice())?;
         writer.write_all(self.proof.as_slice())?;
         writer.write_all(self.total_a.as_slice())?;
         writer.write_all(self.total_b.as_slice())?;
@@ -2705,6 +2885,171 @@ impl molecule::prelude::Builder for AccountBookDataBuilder {
     }
 }
 #[derive(Clone)]
+pub struct LevelShare(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for LevelShare {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for LevelShare {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for LevelShare {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "index", self.index())?;
+        write!(f, ", {}: {}", "pool", self.pool())?;
+        write!(f, " }}")
+    }
+}
+impl ::core::default::Default for LevelShare {
+    fn default() -> Self {
+        let v = molecule::bytes::Bytes::from_static(&Self::DEFAULT_VALUE);
+        LevelShare::new_unchecked(v)
+    }
+}
+impl LevelShare {
+    const DEFAULT_VALUE: [u8; 32] = [
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0,
+    ];
+    pub const TOTAL_SIZE: usize = 32;
+    pub const FIELD_SIZES: [usize; 2] = [16, 16];
+    pub const FIELD_COUNT: usize = 2;
+    pub fn index(&self) -> Uint128 {
+        Uint128::new_unchecked(self.0.slice(0..16))
+    }
+    pub fn pool(&self) -> Uint128 {
+        Uint128::new_unchecked(self.0.slice(16..32))
+    }
+    pub fn as_reader<'r>(&'r self) -> LevelShareReader<'r> {
+        LevelShareReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for LevelShare {
+    type Builder = LevelShareBuilder;
+    const NAME: &'static str = "LevelShare";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        LevelShare(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        LevelShareReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        LevelShareReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder().index(self.index()).pool(self.pool())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct LevelShareReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for LevelShareReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for LevelShareReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for LevelShareReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "index", self.index())?;
+        write!(f, ", {}: {}", "pool", self.pool())?;
+        write!(f, " }}")
+    }
+}
+impl<'r> LevelShareReader<'r> {
+    pub const TOTAL_SIZE: usize = 32;
+    pub const FIELD_SIZES: [usize; 2] = [16, 16];
+    pub const FIELD_COUNT: usize = 2;
+    pub fn index(&self) -> Uint128Reader<'r> {
+        Uint128Reader::new_unchecked(&self.as_slice()[0..16])
+    }
+    pub fn pool(&self) -> Uint128Reader<'r> {
+        Uint128Reader::new_unchecked(&self.as_slice()[16..32])
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for LevelShareReader<'r> {
+    type Entity = LevelShare;
+    const NAME: &'static str = "LevelShareReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        LevelShareReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], _compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len != Self::TOTAL_SIZE {
+            return ve!(Self, TotalSizeNotMatch, Self::TOTAL_SIZE, slice_len);
+        }
+        Ok(())
+    }
+}
+#[derive(Clone, Debug, Default)]
+pub struct LevelShareBuilder {
+    pub(crate) index: Uint128,
+    pub(crate) pool: Uint128,
+}
+impl LevelShareBuilder {
+    pub const TOTAL_SIZE: usize = 32;
+    pub const FIELD_SIZES: [usize; 2] = [16, 16];
+    pub const FIELD_COUNT: usize = 2;
+    pub fn index(mut self, v: Uint128) -> Self {
+        self.index = v;
+        self
+    }
+    pub fn pool(mut self, v: Uint128) -> Self {
+        self.pool = v;
+        self
+    }
+}
+impl molecule::prelude::Builder for LevelShareBuilder {
+    type Entity = LevelShare;
+    const NAME: &'static str = "LevelShareBuilder";
+    fn expected_length(&self) -> usize {
+        Self::TOTAL_SIZE
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        writer.write_all(self.index.as_slice())?;
+        writer.write_all(self.pool.as_slice())?;
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        LevelShare::new_unchecked(inner.into())
+    }
+}
+#[derive(Clone)]
 pub struct AccountBookCellData(molecule::bytes::Bytes);
 impl ::core::fmt::LowerHex for AccountBookCellData {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
@@ -2725,10 +3070,21 @@ impl ::core::fmt::Display for AccountBookCellData {
         write!(f, "{} {{ ", Self::NAME)?;
         write!(f, "{}: {}", "smt_root_hash", self.smt_root_hash())?;
         write!(f, ", {}: {}", "member_count", self.member_count())?;
+        write!(f, ", {}: {}", "a_count", self.a_count())?;
+        write!(f, ", {}: {}", "b_count", self.b_count())?;
+        write!(f, ", {}: {}", "c_count", self.c_count())?;
+        write!(f, ", {}: {}", "a_share", self.a_share())?;
+        write!(f, ", {}: {}", "b_share", self.b_share())?;
+        write!(f, ", {}: {}", "c_share", self.c_share())?;
         write!(f, ", {}: {}", "owner_script_hash", self.owner_script_hash())?;
         write!(f, ", {}: {}", "auther_id", self.auther_id())?;
         write!(f, ", {}: {}", "platform_id", self.platform_id())?;
         write!(f, ", {}: {}", "asset_amount", self.asset_amount())?;
+        write!(f, ", {}: {}", "paused", self.paused())?;
+        write!(f, ", {}: {}", "sale_start", self.sale_start())?;
+        write!(f, ", {}: {}", "sale_end", self.sale_end())?;
+        write!(f, ", {}: {}", "allowlist_root", self.allowlist_root())?;
+        write!(f, ", {}: {}", "allowlist_limit", self.allowlist_limit())?;
         write!(f, ", {}: {}", "a_num", self.a_num())?;
         write!(f, ", {}: {}", "b_num", self.b_num())?;
         write!(f, ", {}: {}", "c_num", self.c_num())?;
@@ -2750,17 +3106,24 @@ impl ::core::default::Default for AccountBookCellData {
     }
 }
 impl AccountBookCellData {
-    const DEFAULT_VALUE: [u8; 230] = [
-        230, 0, 0, 0, 56, 0, 0, 0, 88, 0, 0, 0, 92, 0, 0, 0, 124, 0, 0, 0, 156, 0, 0, 0, 188, 0, 0,
-        0, 204, 0, 0, 0, 208, 0, 0, 0, 212, 0, 0, 0, 216, 0, 0, 0, 218, 0, 0, 0, 221, 0, 0, 0, 225,
+    const DEFAULT_VALUE: [u8; 435] = [
+        179, 1, 0, 0, 100, 0, 0, 0, 132, 0, 0, 0, 136, 0, 0, 0, 140, 0, 0, 0, 144, 0, 0, 0, 148, 0,
+        0, 0, 180, 0, 0, 0, 212, 0, 0, 0, 244, 0, 0, 0, 20, 1, 0, 0, 52, 1, 0, 0, 84, 1, 0, 0, 100,
+        1, 0, 0, 101, 1, 0, 0, 109, 1, 0, 0, 117, 1, 0, 0, 149, 1, 0, 0, 153, 1, 0, 0, 157, 1, 0,
+        0, 161, 1, 0, 0, 165, 1, 0, 0, 167, 1, 0, 0, 170, 1, 0, 0, 174, 1, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
     ];
-    pub const FIELD_COUNT: usize = 13;
+    pub const FIELD_COUNT: usize = 24;
     pub fn total_size(&self) -> usize {
         molecule::unpack_number(self.as_slice()) as usize
     }
@@ -2789,71 +3152,137 @@ impl AccountBookCellData {
         let end = molecule::unpack_number(&slice[12..]) as usize;
         Uint32::new_unchecked(self.0.slice(start..end))
     }
-    pub fn owner_script_hash(&self) -> Byte32 {
+    pub fn a_count(&self) -> Uint32 {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[12..]) as usize;
         let end = molecule::unpack_number(&slice[16..]) as usize;
-        Byte32::new_unchecked(self.0.slice(start..end))
+        Uint32::new_unchecked(self.0.slice(start..end))
     }
-    pub fn auther_id(&self) -> Byte32 {
+    pub fn b_count(&self) -> Uint32 {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[16..]) as usize;
         let end = molecule::unpack_number(&slice[20..]) as usize;
-        Byte32::new_unchecked(self.0.slice(start..end))
+        Uint32::new_unchecked(self.0.slice(start..end))
     }
-    pub fn platform_id(&self) -> Byte32 {
+    pub fn c_count(&self) -> Uint32 {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[20..]) as usize;
         let end = molecule::unpack_number(&slice[24..]) as usize;
-        Byte32::new_unchecked(self.0.slice(start..end))
+        Uint32::new_unchecked(self.0.slice(start..end))
     }
-    pub fn asset_amount(&self) -> Uint128 {
+    pub fn a_share(&self) -> LevelShare {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[24..]) as usize;
         let end = molecule::unpack_number(&slice[28..]) as usize;
-        Uint128::new_unchecked(self.0.slice(start..end))
+        LevelShare::new_unchecked(self.0.slice(start..end))
     }
-    pub fn a_num(&self) -> Uint32 {
+    pub fn b_share(&self) -> LevelShare {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[28..]) as usize;
         let end = molecule::unpack_number(&slice[32..]) as usize;
-        Uint32::new_unchecked(self.0.slice(start..end))
+        LevelShare::new_unchecked(self.0.slice(start..end))
     }
-    pub fn b_num(&self) -> Uint32 {
+    pub fn c_share(&self) -> LevelShare {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[32..]) as usize;
         let end = molecule::unpack_number(&slice[36..]) as usize;
-        Uint32::new_unchecked(self.0.slice(start..end))
+        LevelShare::new_unchecked(self.0.slice(start..end))
     }
-    pub fn c_num(&self) -> Uint32 {
+    pub fn owner_script_hash(&self) -> Byte32 {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[36..]) as usize;
         let end = molecule::unpack_number(&slice[40..]) as usize;
-        Uint32::new_unchecked(self.0.slice(start..end))
+        Byte32::new_unchecked(self.0.slice(start..end))
     }
-    pub fn a_profit(&self) -> AProfit {
+    pub fn auther_id(&self) -> Byte32 {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[40..]) as usize;
         let end = molecule::unpack_number(&slice[44..]) as usize;
-        AProfit::new_unchecked(self.0.slice(start..end))
+        Byte32::new_unchecked(self.0.slice(start..end))
     }
-    pub fn b_profit(&self) -> BProfit {
+    pub fn platform_id(&self) -> Byte32 {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[44..]) as usize;
         let end = molecule::unpack_number(&slice[48..]) as usize;
-        BProfit::new_unchecked(self.0.slice(start..end))
+        Byte32::new_unchecked(self.0.slice(start..end))
     }
-    pub fn c_profit(&self) -> CProfit {
+    pub fn asset_amount(&self) -> Uint128 {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[48..]) as usize;
         let end = molecule::unpack_number(&slice[52..]) as usize;
+        Uint128::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn paused(&self) -> Byte {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[52..]) as usize;
+        let end = molecule::unpack_number(&slice[56..]) as usize;
+        Byte::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn sale_start(&self) -> Uint64 {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[56..]) as usize;
+        let end = molecule::unpack_number(&slice[60..]) as usize;
+        Uint64::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn sale_end(&self) -> Uint64 {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[60..]) as usize;
+        let end = molecule::unpack_number(&slice[64..]) as usize;
+        Uint64::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn allowlist_root(&self) -> Byte32 {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[64..]) as usize;
+        let end = molecule::unpack_number(&slice[68..]) as usize;
+        Byte32::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn allowlist_limit(&self) -> Uint32 {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[68..]) as usize;
+        let end = molecule::unpack_number(&slice[72..]) as usize;
+        Uint32::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn a_num(&self) -> Uint32 {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[72..]) as usize;
+        let end = molecule::unpack_number(&slice[76..]) as usize;
+        Uint32::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn b_num(&self) -> Uint32 {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[76..]) as usize;
+        let end = molecule::unpack_number(&slice[80..]) as usize;
+        Uint32::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn c_num(&self) -> Uint32 {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[80..]) as usize;
+        let end = molecule::unpack_number(&slice[84..]) as usize;
+        Uint32::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn a_profit(&self) -> AProfit {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[84..]) as usize;
+        let end = molecule::unpack_number(&slice[88..]) as usize;
+        AProfit::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn b_profit(&self) -> BProfit {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[88..]) as usize;
+        let end = molecule::unpack_number(&slice[92..]) as usize;
+        BProfit::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn c_profit(&self) -> CProfit {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[92..]) as usize;
+        let end = molecule::unpack_number(&slice[96..]) as usize;
         CProfit::new_unchecked(self.0.slice(start..end))
     }
     pub fn d_profit(&self) -> DProfit {
         let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[52..]) as usize;
+        let start = molecule::unpack_number(&slice[96..]) as usize;
         if self.has_extra_fields() {
-            let end = molecule::unpack_number(&slice[56..]) as usize;
+            let end = molecule::unpack_number(&slice[100..]) as usize;
             DProfit::new_unchecked(self.0.slice(start..end))
         } else {
             DProfit::new_unchecked(self.0.slice(start..))
@@ -2888,10 +3317,21 @@ impl molecule::prelude::Entity for AccountBookCellData {
         Self::new_builder()
             .smt_root_hash(self.smt_root_hash())
             .member_count(self.member_count())
+            .a_count(self.a_count())
+            .b_count(self.b_count())
+            .c_count(self.c_count())
+            .a_share(self.a_share())
+            .b_share(self.b_share())
+            .c_share(self.c_share())
             .owner_script_hash(self.owner_script_hash())
             .auther_id(self.auther_id())
             .platform_id(self.platform_id())
             .asset_amount(self.asset_amount())
+            .paused(self.paused())
+            .sale_start(self.sale_start())
+            .sale_end(self.sale_end())
+            .allowlist_root(self.allowlist_root())
+            .allowlist_limit(self.allowlist_limit())
             .a_num(self.a_num())
             .b_num(self.b_num())
             .c_num(self.c_num())
@@ -2922,10 +3362,21 @@ impl<'r> ::core::fmt::Display for AccountBookCellDataReader<'r> {
         write!(f, "{} {{ ", Self::NAME)?;
         write!(f, "{}: {}", "smt_root_hash", self.smt_root_hash())?;
         write!(f, ", {}: {}", "member_count", self.member_count())?;
+        write!(f, ", {}: {}", "a_count", self.a_count())?;
+        write!(f, ", {}: {}", "b_count", self.b_count())?;
+        write!(f, ", {}: {}", "c_count", self.c_count())?;
+        write!(f, ", {}: {}", "a_share", self.a_share())?;
+        write!(f, ", {}: {}", "b_share", self.b_share())?;
+        write!(f, ", {}: {}", "c_share", self.c_share())?;
         write!(f, ", {}: {}", "owner_script_hash", self.owner_script_hash())?;
         write!(f, ", {}: {}", "auther_id", self.auther_id())?;
         write!(f, ", {}: {}", "platform_id", self.platform_id())?;
         write!(f, ", {}: {}", "asset_amount", self.asset_amount())?;
+        write!(f, ", {}: {}", "paused", self.paused())?;
+        write!(f, ", {}: {}", "sale_start", self.sale_start())?;
+        write!(f, ", {}: {}", "sale_end", self.sale_end())?;
+        write!(f, ", {}: {}", "allowlist_root", self.allowlist_root())?;
+        write!(f, ", {}: {}", "allowlist_limit", self.allowlist_limit())?;
         write!(f, ", {}: {}", "a_num", self.a_num())?;
         write!(f, ", {}: {}", "b_num", self.b_num())?;
         write!(f, ", {}: {}", "c_num", self.c_num())?;
@@ -2941,7 +3392,7 @@ impl<'r> ::core::fmt::Display for AccountBookCellDataReader<'r> {
     }
 }
 impl<'r> AccountBookCellDataReader<'r> {
-    pub const FIELD_COUNT: usize = 13;
+    pub const FIELD_COUNT: usize = 24;
     pub fn total_size(&self) -> usize {
         molecule::unpack_number(self.as_slice()) as usize
     }
@@ -2970,71 +3421,137 @@ impl<'r> AccountBookCellDataReader<'r> {
         let end = molecule::unpack_number(&slice[12..]) as usize;
         Uint32Reader::new_unchecked(&self.as_slice()[start..end])
     }
-    pub fn owner_script_hash(&self) -> Byte32Reader<'r> {
+    pub fn a_count(&self) -> Uint32Reader<'r> {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[12..]) as usize;
         let end = molecule::unpack_number(&slice[16..]) as usize;
-        Byte32Reader::new_unchecked(&self.as_slice()[start..end])
+        Uint32Reader::new_unchecked(&self.as_slice()[start..end])
     }
-    pub fn auther_id(&self) -> Byte32Reader<'r> {
+    pub fn b_count(&self) -> Uint32Reader<'r> {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[16..]) as usize;
         let end = molecule::unpack_number(&slice[20..]) as usize;
-        Byte32Reader::new_unchecked(&self.as_slice()[start..end])
+        Uint32Reader::new_unchecked(&self.as_slice()[start..end])
     }
-    pub fn platform_id(&self) -> Byte32Reader<'r> {
+    pub fn c_count(&self) -> Uint32Reader<'r> {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[20..]) as usize;
         let end = molecule::unpack_number(&slice[24..]) as usize;
-        Byte32Reader::new_unchecked(&self.as_slice()[start..end])
+        Uint32Reader::new_unchecked(&self.as_slice()[start..end])
     }
-    pub fn asset_amount(&self) -> Uint128Reader<'r> {
+    pub fn a_share(&self) -> LevelShareReader<'r> {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[24..]) as usize;
         let end = molecule::unpack_number(&slice[28..]) as usize;
-        Uint128Reader::new_unchecked(&self.as_slice()[start..end])
+        LevelShareReader::new_unchecked(&self.as_slice()[start..end])
     }
-    pub fn a_num(&self) -> Uint32Reader<'r> {
+    pub fn b_share(&self) -> LevelShareReader<'r> {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[28..]) as usize;
         let end = molecule::unpack_number(&slice[32..]) as usize;
-        Uint32Reader::new_unchecked(&self.as_slice()[start..end])
+        LevelShareReader::new_unchecked(&self.as_slice()[start..end])
     }
-    pub fn b_num(&self) -> Uint32Reader<'r> {
+    pub fn c_share(&self) -> LevelShareReader<'r> {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[32..]) as usize;
         let end = molecule::unpack_number(&slice[36..]) as usize;
-        Uint32Reader::new_unchecked(&self.as_slice()[start..end])
+        LevelShareReader::new_unchecked(&self.as_slice()[start..end])
     }
-    pub fn c_num(&self) -> Uint32Reader<'r> {
+    pub fn owner_script_hash(&self) -> Byte32Reader<'r> {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[36..]) as usize;
         let end = molecule::unpack_number(&slice[40..]) as usize;
-        Uint32Reader::new_unchecked(&self.as_slice()[start..end])
+        Byte32Reader::new_unchecked(&self.as_slice()[start..end])
     }
-    pub fn a_profit(&self) -> AProfitReader<'r> {
+    pub fn auther_id(&self) -> Byte32Reader<'r> {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[40..]) as usize;
         let end = molecule::unpack_number(&slice[44..]) as usize;
-        AProfitReader::new_unchecked(&self.as_slice()[start..end])
+        Byte32Reader::new_unchecked(&self.as_slice()[start..end])
     }
-    pub fn b_profit(&self) -> BProfitReader<'r> {
+    pub fn platform_id(&self) -> Byte32Reader<'r> {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[44..]) as usize;
         let end = molecule::unpack_number(&slice[48..]) as usize;
-        BProfitReader::new_unchecked(&self.as_slice()[start..end])
+        Byte32Reader::new_unchecked(&self.as_slice()[start..end])
     }
-    pub fn c_profit(&self) -> CProfitReader<'r> {
+    pub fn asset_amount(&self) -> Uint128Reader<'r> {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[48..]) as usize;
         let end = molecule::unpack_number(&slice[52..]) as usize;
+        Uint128Reader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn paused(&self) -> ByteReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[52..]) as usize;
+        let end = molecule::unpack_number(&slice[56..]) as usize;
+        ByteReader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn sale_start(&self) -> Uint64Reader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[56..]) as usize;
+        let end = molecule::unpack_number(&slice[60..]) as usize;
+        Uint64Reader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn sale_end(&self) -> Uint64Reader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[60..]) as usize;
+        let end = molecule::unpack_number(&slice[64..]) as usize;
+        Uint64Reader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn allowlist_root(&self) -> Byte32Reader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[64..]) as usize;
+        let end = molecule::unpack_number(&slice[68..]) as usize;
+        Byte32Reader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn allowlist_limit(&self) -> Uint32Reader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[68..]) as usize;
+        let end = molecule::unpack_number(&slice[72..]) as usize;
+        Uint32Reader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn a_num(&self) -> Uint32Reader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[72..]) as usize;
+        let end = molecule::unpack_number(&slice[76..]) as usize;
+        Uint32Reader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn b_num(&self) -> Uint32Reader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[76..]) as usize;
+        let end = molecule::unpack_number(&slice[80..]) as usize;
+        Uint32Reader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn c_num(&self) -> Uint32Reader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[80..]) as usize;
+        let end = molecule::unpack_number(&slice[84..]) as usize;
+        Uint32Reader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn a_profit(&self) -> AProfitReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[84..]) as usize;
+        let end = molecule::unpack_number(&slice[88..]) as usize;
+        AProfitReader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn b_profit(&self) -> BProfitReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[88..]) as usize;
+        let end = molecule::unpack_number(&slice[92..]) as usize;
+        BProfitReader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn c_profit(&self) -> CProfitReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[92..]) as usize;
+        let end = molecule::unpack_number(&slice[96..]) as usize;
         CProfitReader::new_unchecked(&self.as_slice()[start..end])
     }
     pub fn d_profit(&self) -> DProfitReader<'r> {
         let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[52..]) as usize;
+        let start = molecule::unpack_number(&slice[96..]) as usize;
         if self.has_extra_fields() {
-            let end = molecule::unpack_number(&slice[56..]) as usize;
+            let end = molecule::unpack_number(&slice[100..]) as usize;
             DProfitReader::new_unchecked(&self.as_slice()[start..end])
         } else {
             DProfitReader::new_unchecked(&self.as_slice()[start..])
@@ -3089,17 +3606,28 @@ impl<'r> molecule::prelude::Reader<'r> for AccountBookCellDataReader<'r> {
         }
         Byte32Reader::verify(&slice[offsets[0]..offsets[1]], compatible)?;
         Uint32Reader::verify(&slice[offsets[1]..offsets[2]], compatible)?;
-        Byte32Reader::verify(&slice[offsets[2]..offsets[3]], compatible)?;
-        Byte32Reader::verify(&slice[offsets[3]..offsets[4]], compatible)?;
-        Byte32Reader::verify(&slice[offsets[4]..offsets[5]], compatible)?;
-        Uint128Reader::verify(&slice[offsets[5]..offsets[6]], compatible)?;
-        Uint32Reader::verify(&slice[offsets[6]..offsets[7]], compatible)?;
-        Uint32Reader::verify(&slice[offsets[7]..offsets[8]], compatible)?;
-        Uint32Reader::verify(&slice[offsets[8]..offsets[9]], compatible)?;
-        AProfitReader::verify(&slice[offsets[9]..offsets[10]], compatible)?;
-        BProfitReader::verify(&slice[offsets[10]..offsets[11]], compatible)?;
-        CProfitReader::verify(&slice[offsets[11]..offsets[12]], compatible)?;
-        DProfitReader::verify(&slice[offsets[12]..offsets[13]], compatible)?;
+        Uint32Reader::verify(&slice[offsets[2]..offsets[3]], compatible)?;
+        Uint32Reader::verify(&slice[offsets[3]..offsets[4]], compatible)?;
+        Uint32Reader::verify(&slice[offsets[4]..offsets[5]], compatible)?;
+        LevelShareReader::verify(&slice[offsets[5]..offsets[6]], compatible)?;
+        LevelShareReader::verify(&slice[offsets[6]..offsets[7]], compatible)?;
+        LevelShareReader::verify(&slice[offsets[7]..offsets[8]], compatible)?;
+        Byte32Reader::verify(&slice[offsets[8]..offsets[9]], compatible)?;
+        Byte32Reader::verify(&slice[offsets[9]..offsets[10]], compatible)?;
+        Byte32Reader::verify(&slice[offsets[10]..offsets[11]], compatible)?;
+        Uint128Reader::verify(&slice[offsets[11]..offsets[12]], compatible)?;
+        ByteReader::verify(&slice[offsets[12]..offsets[13]], compatible)?;
+        Uint64Reader::verify(&slice[offsets[13]..offsets[14]], compatible)?;
+        Uint64Reader::verify(&slice[offsets[14]..offsets[15]], compatible)?;
+        Byte32Reader::verify(&slice[offsets[15]..offsets[16]], compatible)?;
+        Uint32Reader::verify(&slice[offsets[16]..offsets[17]], compatible)?;
+        Uint32Reader::verify(&slice[offsets[17]..offsets[18]], compatible)?;
+        Uint32Reader::verify(&slice[offsets[18]..offsets[19]], compatible)?;
+        Uint32Reader::verify(&slice[offsets[19]..offsets[20]], compatible)?;
+        AProfitReader::verify(&slice[offsets[20]..offsets[21]], compatible)?;
+        BProfitReader::verify(&slice[offsets[21]..offsets[22]], compatible)?;
+        CProfitReader::verify(&slice[offsets[22]..offsets[23]], compatible)?;
+        DProfitReader::verify(&slice[offsets[23]..offsets[24]], compatible)?;
         Ok(())
     }
 }
@@ -3107,10 +3635,21 @@ impl<'r> molecule::prelude::Reader<'r> for AccountBookCellDataReader<'r> {
 pub struct AccountBookCellDataBuilder {
     pub(crate) smt_root_hash: Byte32,
     pub(crate) member_count: Uint32,
+    pub(crate) a_count: Uint32,
+    pub(crate) b_count: Uint32,
+    pub(crate) c_count: Uint32,
+    pub(crate) a_share: LevelShare,
+    pub(crate) b_share: LevelShare,
+    pub(crate) c_share: LevelShare,
     pub(crate) owner_script_hash: Byte32,
     pub(crate) auther_id: Byte32,
     pub(crate) platform_id: Byte32,
     pub(crate) asset_amount: Uint128,
+    pub(crate) paused: Byte,
+    pub(crate) sale_start: Uint64,
+    pub(crate) sale_end: Uint64,
+    pub(crate) allowlist_root: Byte32,
+    pub(crate) allowlist_limit: Uint32,
     pub(crate) a_num: Uint32,
     pub(crate) b_num: Uint32,
     pub(crate) c_num: Uint32,
@@ -3120,7 +3659,7 @@ pub struct AccountBookCellDataBuilder {
     pub(crate) d_profit: DProfit,
 }
 impl AccountBookCellDataBuilder {
-    pub const FIELD_COUNT: usize = 13;
+    pub const FIELD_COUNT: usize = 24;
     pub fn smt_root_hash(mut self, v: Byte32) -> Self {
         self.smt_root_hash = v;
         self
@@ -3129,6 +3668,30 @@ impl AccountBookCellDataBuilder {
         self.member_count = v;
         self
     }
+    pub fn a_count(mut self, v: Uint32) -> Self {
+        self.a_count = v;
+        self
+    }
+    pub fn b_count(mut self, v: Uint32) -> Self {
+        self.b_count = v;
+        self
+    }
+    pub fn c_count(mut self, v: Uint32) -> Self {
+        self.c_count = v;
+        self
+    }
+    pub fn a_share(mut self, v: LevelShare) -> Self {
+        self.a_share = v;
+        self
+    }
+    pub fn b_share(mut self, v: LevelShare) -> Self {
+        self.b_share = v;
+        self
+    }
+    pub fn c_share(mut self, v: LevelShare) -> Self {
+        self.c_share = v;
+        self
+    }
     pub fn owner_script_hash(mut self, v: Byte32) -> Self {
         self.owner_script_hash = v;
         self
@@ -3145,6 +3708,26 @@ impl AccountBookCellDataBuilder {
         self.asset_amount = v;
         self
     }
+    pub fn paused(mut self, v: Byte) -> Self {
+        self.paused = v;
+        self
+    }
+    pub fn sale_start(mut self, v: Uint64) -> Self {
+        self.sale_start = v;
+        self
+    }
+    pub fn sale_end(mut self, v: Uint64) -> Self {
+        self.sale_end = v;
+        self
+    }
+    pub fn allowlist_root(mut self, v: Byte32) -> Self {
+        self.allowlist_root = v;
+        self
+    }
+    pub fn allowlist_limit(mut self, v: Uint32) -> Self {
+        self.allowlist_limit = v;
+        self
+    }
     pub fn a_num(mut self, v: Uint32) -> Self {
         self.a_num = v;
         self
@@ -3181,10 +3764,21 @@ impl molecule::prelude::Builder for AccountBookCellDataBuilder {
         molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1)
             + self.smt_root_hash.as_slice().len()
             + self.member_count.as_slice().len()
+            + self.a_count.as_slice().len()
+            + self.b_count.as_slice().len()
+            + self.c_count.as_slice().len()
+            + self.a_share.as_slice().len()
+            + self.b_share.as_slice().len()
+            + self.c_share.as_slice().len()
             + self.owner_script_hash.as_slice().len()
             + self.auther_id.as_slice().len()
             + self.platform_id.as_slice().len()
             + self.asset_amount.as_slice().len()
+            + self.paused.as_slice().len()
+            + self.sale_start.as_slice().len()
+            + self.sale_end.as_slice().len()
+            + self.allowlist_root.as_slice().len()
+            + self.allowlist_limit.as_slice().len()
             + self.a_num.as_slice().len()
             + self.b_num.as_slice().len()
             + self.c_num.as_slice().len()
@@ -3201,6 +3795,18 @@ impl molecule::prelude::Builder for AccountBookCellDataBuilder {
         offsets.push(total_size);
         total_size += self.member_count.as_slice().len();
         offsets.push(total_size);
+        total_size += self.a_count.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.b_count.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.c_count.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.a_share.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.b_share.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.c_share.as_slice().len();
+        offsets.push(total_size);
         total_size += self.owner_script_hash.as_slice().len();
         offsets.push(total_size);
         total_size += self.auther_id.as_slice().len();
@@ -3209,6 +3815,16 @@ impl molecule::prelude::Builder for AccountBookCellDataBuilder {
         offsets.push(total_size);
         total_size += self.asset_amount.as_slice().len();
         offsets.push(total_size);
+        total_size += self.paused.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.sale_start.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.sale_end.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.allowlist_root.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.allowlist_limit.as_slice().len();
+        offsets.push(total_size);
         total_size += self.a_num.as_slice().len();
         offsets.push(total_size);
         total_size += self.b_num.as_slice().len();
@@ -3228,10 +3844,21 @@ impl molecule::prelude::Builder for AccountBookCellDataBuilder {
         }
         writer.write_all(self.smt_root_hash.as_slice())?;
         writer.write_all(self.member_count.as_slice())?;
+        writer.write_all(self.a_count.as_slice())?;
+        writer.write_all(self.b_count.as_slice())?;
+        writer.write_all(self.c_count.as_slice())?;
+        writer.write_all(self.a_share.as_slice())?;
+        writer.write_all(self.b_share.as_slice())?;
+        writer.write_all(self.c_share.as_slice())?;
         writer.write_all(self.owner_script_hash.as_slice())?;
         writer.write_all(self.auther_id.as_slice())?;
         writer.write_all(self.platform_id.as_slice())?;
         writer.write_all(self.asset_amount.as_slice())?;
+        writer.write_all(self.paused.as_slice())?;
+        writer.write_all(self.sale_start.as_slice())?;
+        writer.write_all(self.sale_end.as_slice())?;
+        writer.write_all(self.allowlist_root.as_slice())?;
+        writer.write_all(self.allowlist_limit.as_slice())?;
         writer.write_all(self.a_num.as_slice())?;
         writer.write_all(self.b_num.as_slice())?;
         writer.write_all(self.c_num.as_slice())?;
@@ -3248,3 +3875,1183 @@ impl molecule::prelude::Builder for AccountBookCellDataBuilder {
         AccountBookCellData::new_unchecked(inner.into())
     }
 }
+#[derive(Clone)]
+pub struct MemberLeaf(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for MemberLeaf {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for MemberLeaf {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for MemberLeaf {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "level", self.level())?;
+        write!(f, ", {}: {}", "withdrawn", self.withdrawn())?;
+        write!(f, ", {}: {}", "joined_at", self.joined_at())?;
+        write!(f, ", {}: {}", "share_index", self.share_index())?;
+        write!(f, " }}")
+    }
+}
+impl ::core::default::Default for MemberLeaf {
+    fn default() -> Self {
+        let v = molecule::bytes::Bytes::from_static(&Self::DEFAULT_VALUE);
+        MemberLeaf::new_unchecked(v)
+    }
+}
+impl MemberLeaf {
+    const DEFAULT_VALUE: [u8; 37] = [
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0,
+    ];
+    pub const TOTAL_SIZE: usize = 37;
+    pub const FIELD_SIZES: [usize; 4] = [1, 16, 4, 16];
+    pub const FIELD_COUNT: usize = 4;
+    pub fn level(&self) -> Byte {
+        Byte::new_unchecked(self.0.slice(0..1))
+    }
+    pub fn withdrawn(&self) -> Uint128 {
+        Uint128::new_unchecked(self.0.slice(1..17))
+    }
+    pub fn joined_at(&self) -> Uint32 {
+        Uint32::new_unchecked(self.0.slice(17..21))
+    }
+    pub fn share_index(&self) -> Uint128 {
+        Uint128::new_unchecked(self.0.slice(21..37))
+    }
+    pub fn as_reader<'r>(&'r self) -> MemberLeafReader<'r> {
+        MemberLeafReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for MemberLeaf {
+    type Builder = MemberLeafBuilder;
+    const NAME: &'static str = "MemberLeaf";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        MemberLeaf(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        MemberLeafReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        MemberLeafReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder()
+            .level(self.level())
+            .withdrawn(self.withdrawn())
+            .joined_at(self.joined_at())
+            .share_index(self.share_index())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct MemberLeafReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for MemberLeafReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for MemberLeafReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for MemberLeafReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "level", self.level())?;
+        write!(f, ", {}: {}", "withdrawn", self.withdrawn())?;
+        write!(f, ", {}: {}", "joined_at", self.joined_at())?;
+        write!(f, ", {}: {}", "share_index", self.share_index())?;
+        write!(f, " }}")
+    }
+}
+impl<'r> MemberLeafReader<'r> {
+    pub const TOTAL_SIZE: usize = 37;
+    pub const FIELD_SIZES: [usize; 4] = [1, 16, 4, 16];
+    pub const FIELD_COUNT: usize = 4;
+    pub fn level(&self) -> ByteReader<'r> {
+        ByteReader::new_unchecked(&self.as_slice()[0..1])
+    }
+    pub fn withdrawn(&self) -> Uint128Reader<'r> {
+        Uint128Reader::new_unchecked(&self.as_slice()[1..17])
+    }
+    pub fn joined_at(&self) -> Uint32Reader<'r> {
+        Uint32Reader::new_unchecked(&self.as_slice()[17..21])
+    }
+    pub fn share_index(&self) -> Uint128Reader<'r> {
+        Uint128Reader::new_unchecked(&self.as_slice()[21..37])
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for MemberLeafReader<'r> {
+    type Entity = MemberLeaf;
+    const NAME: &'static str = "MemberLeafReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        MemberLeafReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], _compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len != Self::TOTAL_SIZE {
+            return ve!(Self, TotalSizeNotMatch, Self::TOTAL_SIZE, slice_len);
+        }
+        Ok(())
+    }
+}
+#[derive(Clone, Debug, Default)]
+pub struct MemberLeafBuilder {
+    pub(crate) level: Byte,
+    pub(crate) withdrawn: Uint128,
+    pub(crate) joined_at: Uint32,
+    pub(crate) share_index: Uint128,
+}
+impl MemberLeafBuilder {
+    pub const TOTAL_SIZE: usize = 37;
+    pub const FIELD_SIZES: [usize; 4] = [1, 16, 4, 16];
+    pub const FIELD_COUNT: usize = 4;
+    pub fn level(mut self, v: Byte) -> Self {
+        self.level = v;
+        self
+    }
+    pub fn withdrawn(mut self, v: Uint128) -> Self {
+        self.withdrawn = v;
+        self
+    }
+    pub fn joined_at(mut self, v: Uint32) -> Self {
+        self.joined_at = v;
+        self
+    }
+    pub fn share_index(mut self, v: Uint128) -> Self {
+        self.share_index = v;
+        self
+    }
+}
+impl molecule::prelude::Builder for MemberLeafBuilder {
+    type Entity = MemberLeaf;
+    const NAME: &'static str = "MemberLeafBuilder";
+    fn expected_length(&self) -> usize {
+        Self::TOTAL_SIZE
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        writer.write_all(self.level.as_slice())?;
+        writer.write_all(self.withdrawn.as_slice())?;
+        writer.write_all(self.joined_at.as_slice())?;
+        writer.write_all(self.share_index.as_slice())?;
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        MemberLeaf::new_unchecked(inner.into())
+    }
+}
+#[derive(Clone)]
+pub struct MemberLeafVec(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for MemberLeafVec {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for MemberLeafVec {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for MemberLeafVec {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} [", Self::NAME)?;
+        for i in 0..self.len() {
+            if i == 0 {
+                write!(f, "{}", self.get_unchecked(i))?;
+            } else {
+                write!(f, ", {}", self.get_unchecked(i))?;
+            }
+        }
+        write!(f, "]")
+    }
+}
+impl ::core::default::Default for MemberLeafVec {
+    fn default() -> Self {
+        let v = molecule::bytes::Bytes::from_static(&Self::DEFAULT_VALUE);
+        MemberLeafVec::new_unchecked(v)
+    }
+}
+impl MemberLeafVec {
+    const DEFAULT_VALUE: [u8; 4] = [0, 0, 0, 0];
+    pub const ITEM_SIZE: usize = 37;
+    pub fn total_size(&self) -> usize {
+        molecule::NUMBER_SIZE + Self::ITEM_SIZE * self.item_count()
+    }
+    pub fn item_count(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn len(&self) -> usize {
+        self.item_count()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    pub fn get(&self, idx: usize) -> Option<MemberLeaf> {
+        if idx >= self.len() {
+            None
+        } else {
+            Some(self.get_unchecked(idx))
+        }
+    }
+    pub fn get_unchecked(&self, idx: usize) -> MemberLeaf {
+        let start = molecule::NUMBER_SIZE + Self::ITEM_SIZE * idx;
+        let end = start + Self::ITEM_SIZE;
+        MemberLeaf::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn as_reader<'r>(&'r self) -> MemberLeafVecReader<'r> {
+        MemberLeafVecReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for MemberLeafVec {
+    type Builder = MemberLeafVecBuilder;
+    const NAME: &'static str = "MemberLeafVec";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        MemberLeafVec(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        MemberLeafVecReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        MemberLeafVecReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder().extend(self.into_iter())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct MemberLeafVecReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for MemberLeafVecReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for MemberLeafVecReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for MemberLeafVecReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} [", Self::NAME)?;
+        for i in 0..self.len() {
+            if i == 0 {
+                write!(f, "{}", self.get_unchecked(i))?;
+            } else {
+                write!(f, ", {}", self.get_unchecked(i))?;
+            }
+        }
+        write!(f, "]")
+    }
+}
+impl<'r> MemberLeafVecReader<'r> {
+    pub const ITEM_SIZE: usize = 37;
+    pub fn total_size(&self) -> usize {
+        molecule::NUMBER_SIZE + Self::ITEM_SIZE * self.item_count()
+    }
+    pub fn item_count(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn len(&self) -> usize {
+        self.item_count()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    pub fn get(&self, idx: usize) -> Option<MemberLeafReader<'r>> {
+        if idx >= self.len() {
+            None
+        } else {
+            Some(self.get_unchecked(idx))
+        }
+    }
+    pub fn get_unchecked(&self, idx: usize) -> MemberLeafReader<'r> {
+        let start = molecule::NUMBER_SIZE + Self::ITEM_SIZE * idx;
+        let end = start + Self::ITEM_SIZE;
+        MemberLeafReader::new_unchecked(&self.as_slice()[start..end])
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for MemberLeafVecReader<'r> {
+    type Entity = MemberLeafVec;
+    const NAME: &'static str = "MemberLeafVecReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        MemberLeafVecReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], _compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len < molecule::NUMBER_SIZE {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
+        }
+        let item_count = molecule::unpack_number(slice) as usize;
+        if item_count == 0 {
+            if slice_len != molecule::NUMBER_SIZE {
+                return ve!(Self, TotalSizeNotMatch, molecule::NUMBER_SIZE, slice_len);
+            }
+            return Ok(());
+        }
+        let total_size = molecule::NUMBER_SIZE + Self::ITEM_SIZE * item_count;
+        if slice_len != total_size {
+            return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
+        }
+        Ok(())
+    }
+}
+#[derive(Clone, Debug, Default)]
+pub struct MemberLeafVecBuilder(pub(crate) Vec<MemberLeaf>);
+impl MemberLeafVecBuilder {
+    pub const ITEM_SIZE: usize = 37;
+    pub fn set(mut self, v: Vec<MemberLeaf>) -> Self {
+        self.0 = v;
+        self
+    }
+    pub fn push(mut self, v: MemberLeaf) -> Self {
+        self.0.push(v);
+        self
+    }
+    pub fn extend<T: ::core::iter::IntoIterator<Item = MemberLeaf>>(mut self, iter: T) -> Self {
+        for elem in iter {
+            self.0.push(elem);
+        }
+        self
+    }
+    pub fn replace(&mut self, index: usize, v: MemberLeaf) -> Option<MemberLeaf> {
+        self.0
+            .get_mut(index)
+            .map(|item| ::core::mem::replace(item, v))
+    }
+}
+impl molecule::prelude::Builder for MemberLeafVecBuilder {
+    type Entity = MemberLeafVec;
+    const NAME: &'static str = "MemberLeafVecBuilder";
+    fn expected_length(&self) -> usize {
+        molecule::NUMBER_SIZE + Self::ITEM_SIZE * self.0.len()
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        writer.write_all(&molecule::pack_number(self.0.len() as molecule::Number))?;
+        for inner in &self.0[..] {
+            writer.write_all(inner.as_slice())?;
+        }
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        MemberLeafVec::new_unchecked(inner.into())
+    }
+}
+pub struct MemberLeafVecIterator(MemberLeafVec, usize, usize);
+impl ::core::iter::Iterator for MemberLeafVecIterator {
+    type Item = MemberLeaf;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.1 >= self.2 {
+            None
+        } else {
+            let ret = self.0.get_unchecked(self.1);
+            self.1 += 1;
+            Some(ret)
+        }
+    }
+}
+impl ::core::iter::ExactSizeIterator for MemberLeafVecIterator {
+    fn len(&self) -> usize {
+        self.2 - self.1
+    }
+}
+impl ::core::iter::IntoIterator for MemberLeafVec {
+    type Item = MemberLeaf;
+    type IntoIter = MemberLeafVecIterator;
+    fn into_iter(self) -> Self::IntoIter {
+        let len = self.len();
+        MemberLeafVecIterator(self, 0, len)
+    }
+}
+impl<'r> MemberLeafVecReader<'r> {
+    pub fn iter<'t>(&'t self) -> MemberLeafVecReaderIterator<'t, 'r> {
+        MemberLeafVecReaderIterator(&self, 0, self.len())
+    }
+}
+pub struct MemberLeafVecReaderIterator<'t, 'r>(&'t MemberLeafVecReader<'r>, usize, usize);
+impl<'t: 'r, 'r> ::core::iter::Iterator for MemberLeafVecReaderIterator<'t, 'r> {
+    type Item = MemberLeafReader<'t>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.1 >= self.2 {
+            None
+        } else {
+            let ret = self.0.get_unchecked(self.1);
+            self.1 += 1;
+            Some(ret)
+        }
+    }
+}
+impl<'t: 'r, 'r> ::core::iter::ExactSizeIterator for MemberLeafVecReaderIterator<'t, 'r> {
+    fn len(&self) -> usize {
+        self.2 - self.1
+    }
+}
+impl ::core::iter::FromIterator<MemberLeaf> for MemberLeafVec {
+    fn from_iter<T: IntoIterator<Item = MemberLeaf>>(iter: T) -> Self {
+        Self::new_builder().extend(iter).build()
+    }
+}
+#[derive(Clone)]
+pub struct BuyerCount(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for BuyerCount {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for BuyerCount {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for BuyerCount {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "owner_script_hash", self.owner_script_hash())?;
+        write!(f, ", {}: {}", "count", self.count())?;
+        write!(f, " }}")
+    }
+}
+impl ::core::default::Default for BuyerCount {
+    fn default() -> Self {
+        let v = molecule::bytes::Bytes::from_static(&Self::DEFAULT_VALUE);
+        BuyerCount::new_unchecked(v)
+    }
+}
+impl BuyerCount {
+    const DEFAULT_VALUE: [u8; 36] = [
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0,
+    ];
+    pub const TOTAL_SIZE: usize = 36;
+    pub const FIELD_SIZES: [usize; 2] = [32, 4];
+    pub const FIELD_COUNT: usize = 2;
+    pub fn owner_script_hash(&self) -> Byte32 {
+        Byte32::new_unchecked(self.0.slice(0..32))
+    }
+    pub fn count(&self) -> Uint32 {
+        Uint32::new_unchecked(self.0.slice(32..36))
+    }
+    pub fn as_reader<'r>(&'r self) -> BuyerCountReader<'r> {
+        BuyerCountReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for BuyerCount {
+    type Builder = BuyerCountBuilder;
+    const NAME: &'static str = "BuyerCount";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        BuyerCount(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        BuyerCountReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        BuyerCountReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder()
+            .owner_script_hash(self.owner_script_hash())
+            .count(self.count())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct BuyerCountReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for BuyerCountReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for BuyerCountReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for BuyerCountReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "owner_script_hash", self.owner_script_hash())?;
+        write!(f, ", {}: {}", "count", self.count())?;
+        write!(f, " }}")
+    }
+}
+impl<'r> BuyerCountReader<'r> {
+    pub const TOTAL_SIZE: usize = 36;
+    pub const FIELD_SIZES: [usize; 2] = [32, 4];
+    pub const FIELD_COUNT: usize = 2;
+    pub fn owner_script_hash(&self) -> Byte32Reader<'r> {
+        Byte32Reader::new_unchecked(&self.as_slice()[0..32])
+    }
+    pub fn count(&self) -> Uint32Reader<'r> {
+        Uint32Reader::new_unchecked(&self.as_slice()[32..36])
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for BuyerCountReader<'r> {
+    type Entity = BuyerCount;
+    const NAME: &'static str = "BuyerCountReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        BuyerCountReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], _compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len != Self::TOTAL_SIZE {
+            return ve!(Self, TotalSizeNotMatch, Self::TOTAL_SIZE, slice_len);
+        }
+        Ok(())
+    }
+}
+#[derive(Clone, Debug, Default)]
+pub struct BuyerCountBuilder {
+    pub(crate) owner_script_hash: Byte32,
+    pub(crate) count: Uint32,
+}
+impl BuyerCountBuilder {
+    pub const TOTAL_SIZE: usize = 36;
+    pub const FIELD_SIZES: [usize; 2] = [32, 4];
+    pub const FIELD_COUNT: usize = 2;
+    pub fn owner_script_hash(mut self, v: Byte32) -> Self {
+        self.owner_script_hash = v;
+        self
+    }
+    pub fn count(mut self, v: Uint32) -> Self {
+        self.count = v;
+        self
+    }
+}
+impl molecule::prelude::Builder for BuyerCountBuilder {
+    type Entity = BuyerCount;
+    const NAME: &'static str = "BuyerCountBuilder";
+    fn expected_length(&self) -> usize {
+        Self::TOTAL_SIZE
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        writer.write_all(self.owner_script_hash.as_slice())?;
+        writer.write_all(self.count.as_slice())?;
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        BuyerCount::new_unchecked(inner.into())
+    }
+}
+#[derive(Clone)]
+pub struct BuyerCountVec(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for BuyerCountVec {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for BuyerCountVec {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for BuyerCountVec {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} [", Self::NAME)?;
+        for i in 0..self.len() {
+            if i == 0 {
+                write!(f, "{}", self.get_unchecked(i))?;
+            } else {
+                write!(f, ", {}", self.get_unchecked(i))?;
+            }
+        }
+        write!(f, "]")
+    }
+}
+impl ::core::default::Default for BuyerCountVec {
+    fn default() -> Self {
+        let v = molecule::bytes::Bytes::from_static(&Self::DEFAULT_VALUE);
+        BuyerCountVec::new_unchecked(v)
+    }
+}
+impl BuyerCountVec {
+    const DEFAULT_VALUE: [u8; 4] = [0, 0, 0, 0];
+    pub const ITEM_SIZE: usize = 36;
+    pub fn total_size(&self) -> usize {
+        molecule::NUMBER_SIZE + Self::ITEM_SIZE * self.item_count()
+    }
+    pub fn item_count(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn len(&self) -> usize {
+        self.item_count()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    pub fn get(&self, idx: usize) -> Option<BuyerCount> {
+        if idx >= self.len() {
+            None
+        } else {
+            Some(self.get_unchecked(idx))
+        }
+    }
+    pub fn get_unchecked(&self, idx: usize) -> BuyerCount {
+        let start = molecule::NUMBER_SIZE + Self::ITEM_SIZE * idx;
+        let end = start + Self::ITEM_SIZE;
+        BuyerCount::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn as_reader<'r>(&'r self) -> BuyerCountVecReader<'r> {
+        BuyerCountVecReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for BuyerCountVec {
+    type Builder = BuyerCountVecBuilder;
+    const NAME: &'static str = "BuyerCountVec";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        BuyerCountVec(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        BuyerCountVecReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        BuyerCountVecReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder().extend(self.into_iter())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct BuyerCountVecReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for BuyerCountVecReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for BuyerCountVecReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for BuyerCountVecReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} [", Self::NAME)?;
+        for i in 0..self.len() {
+            if i == 0 {
+                write!(f, "{}", self.get_unchecked(i))?;
+            } else {
+                write!(f, ", {}", self.get_unchecked(i))?;
+            }
+        }
+        write!(f, "]")
+    }
+}
+impl<'r> BuyerCountVecReader<'r> {
+    pub const ITEM_SIZE: usize = 36;
+    pub fn total_size(&self) -> usize {
+        molecule::NUMBER_SIZE + Self::ITEM_SIZE * self.item_count()
+    }
+    pub fn item_count(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn len(&self) -> usize {
+        self.item_count()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    pub fn get(&self, idx: usize) -> Option<BuyerCountReader<'r>> {
+        if idx >= self.len() {
+            None
+        } else {
+            Some(self.get_unchecked(idx))
+        }
+    }
+    pub fn get_unchecked(&self, idx: usize) -> BuyerCountReader<'r> {
+        let start = molecule::NUMBER_SIZE + Self::ITEM_SIZE * idx;
+        let end = start + Self::ITEM_SIZE;
+        BuyerCountReader::new_unchecked(&self.as_slice()[start..end])
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for BuyerCountVecReader<'r> {
+    type Entity = BuyerCountVec;
+    const NAME: &'static str = "BuyerCountVecReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        BuyerCountVecReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], _compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len < molecule::NUMBER_SIZE {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
+        }
+        let item_count = molecule::unpack_number(slice) as usize;
+        if item_count == 0 {
+            if slice_len != molecule::NUMBER_SIZE {
+                return ve!(Self, TotalSizeNotMatch, molecule::NUMBER_SIZE, slice_len);
+            }
+            return Ok(());
+        }
+        let total_size = molecule::NUMBER_SIZE + Self::ITEM_SIZE * item_count;
+        if slice_len != total_size {
+            return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
+        }
+        Ok(())
+    }
+}
+#[derive(Clone, Debug, Default)]
+pub struct BuyerCountVecBuilder(pub(crate) Vec<BuyerCount>);
+impl BuyerCountVecBuilder {
+    pub const ITEM_SIZE: usize = 36;
+    pub fn set(mut self, v: Vec<BuyerCount>) -> Self {
+        self.0 = v;
+        self
+    }
+    pub fn push(mut self, v: BuyerCount) -> Self {
+        self.0.push(v);
+        self
+    }
+    pub fn extend<T: ::core::iter::IntoIterator<Item = BuyerCount>>(mut self, iter: T) -> Self {
+        for elem in iter {
+            self.0.push(elem);
+        }
+        self
+    }
+    pub fn replace(&mut self, index: usize, v: BuyerCount) -> Option<BuyerCount> {
+        self.0
+            .get_mut(index)
+            .map(|item| ::core::mem::replace(item, v))
+    }
+}
+impl molecule::prelude::Builder for BuyerCountVecBuilder {
+    type Entity = BuyerCountVec;
+    const NAME: &'static str = "BuyerCountVecBuilder";
+    fn expected_length(&self) -> usize {
+        molecule::NUMBER_SIZE + Self::ITEM_SIZE * self.0.len()
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        writer.write_all(&molecule::pack_number(self.0.len() as molecule::Number))?;
+        for inner in &self.0[..] {
+            writer.write_all(inner.as_slice())?;
+        }
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        BuyerCountVec::new_unchecked(inner.into())
+    }
+}
+pub struct BuyerCountVecIterator(BuyerCountVec, usize, usize);
+impl ::core::iter::Iterator for BuyerCountVecIterator {
+    type Item = BuyerCount;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.1 >= self.2 {
+            None
+        } else {
+            let ret = self.0.get_unchecked(self.1);
+            self.1 += 1;
+            Some(ret)
+        }
+    }
+}
+impl ::core::iter::ExactSizeIterator for BuyerCountVecIterator {
+    fn len(&self) -> usize {
+        self.2 - self.1
+    }
+}
+impl ::core::iter::IntoIterator for BuyerCountVec {
+    type Item = BuyerCount;
+    type IntoIter = BuyerCountVecIterator;
+    fn into_iter(self) -> Self::IntoIter {
+        let len = self.len();
+        BuyerCountVecIterator(self, 0, len)
+    }
+}
+impl<'r> BuyerCountVecReader<'r> {
+    pub fn iter<'t>(&'t self) -> BuyerCountVecReaderIterator<'t, 'r> {
+        BuyerCountVecReaderIterator(&self, 0, self.len())
+    }
+}
+pub struct BuyerCountVecReaderIterator<'t, 'r>(&'t BuyerCountVecReader<'r>, usize, usize);
+impl<'t: 'r, 'r> ::core::iter::Iterator for BuyerCountVecReaderIterator<'t, 'r> {
+    type Item = BuyerCountReader<'t>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.1 >= self.2 {
+            None
+        } else {
+            let ret = self.0.get_unchecked(self.1);
+            self.1 += 1;
+            Some(ret)
+        }
+    }
+}
+impl<'t: 'r, 'r> ::core::iter::ExactSizeIterator for BuyerCountVecReaderIterator<'t, 'r> {
+    fn len(&self) -> usize {
+        self.2 - self.1
+    }
+}
+impl ::core::iter::FromIterator<BuyerCount> for BuyerCountVec {
+    fn from_iter<T: IntoIterator<Item = BuyerCount>>(iter: T) -> Self {
+        Self::new_builder().extend(iter).build()
+    }
+}
+#[derive(Clone)]
+pub struct AllowlistWitness(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for AllowlistWitness {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for AllowlistWitness {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for AllowlistWitness {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "proof", self.proof())?;
+        write!(f, ", {}: {}", "buyers", self.buyers())?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl ::core::default::Default for AllowlistWitness {
+    fn default() -> Self {
+        let v = molecule::bytes::Bytes::from_static(&Self::DEFAULT_VALUE);
+        AllowlistWitness::new_unchecked(v)
+    }
+}
+impl AllowlistWitness {
+    const DEFAULT_VALUE: [u8; 20] = [
+        20, 0, 0, 0, 12, 0, 0, 0, 16, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    ];
+    pub const FIELD_COUNT: usize = 2;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn proof(&self) -> Bytes {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        let end = molecule::unpack_number(&slice[8..]) as usize;
+        Bytes::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn buyers(&self) -> BuyerCountVec {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[8..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[12..]) as usize;
+            BuyerCountVec::new_unchecked(self.0.slice(start..end))
+        } else {
+            BuyerCountVec::new_unchecked(self.0.slice(start..))
+        }
+    }
+    pub fn as_reader<'r>(&'r self) -> AllowlistWitnessReader<'r> {
+        AllowlistWitnessReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for AllowlistWitness {
+    type Builder = AllowlistWitnessBuilder;
+    const NAME: &'static str = "AllowlistWitness";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        AllowlistWitness(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        AllowlistWitnessReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        AllowlistWitnessReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder()
+            .proof(self.proof())
+            .buyers(self.buyers())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct AllowlistWitnessReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for AllowlistWitnessReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for AllowlistWitnessReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for AllowlistWitnessReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "proof", self.proof())?;
+        write!(f, ", {}: {}", "buyers", self.buyers())?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl<'r> AllowlistWitnessReader<'r> {
+    pub const FIELD_COUNT: usize = 2;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn proof(&self) -> BytesReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        let end = molecule::unpack_number(&slice[8..]) as usize;
+        BytesReader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn buyers(&self) -> BuyerCountVecReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[8..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[12..]) as usize;
+            BuyerCountVecReader::new_unchecked(&self.as_slice()[start..end])
+        } else {
+            BuyerCountVecReader::new_unchecked(&self.as_slice()[start..])
+        }
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for AllowlistWitnessReader<'r> {
+    type Entity = AllowlistWitness;
+    const NAME: &'static str = "AllowlistWitnessReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        AllowlistWitnessReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len < molecule::NUMBER_SIZE {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
+        }
+        let total_size = molecule::unpack_number(slice) as usize;
+        if slice_len != total_size {
+            return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
+        }
+        if slice_len < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE * 2, slice_len);
+        }
+        let offset_first = molecule::unpack_number(&slice[molecule::NUMBER_SIZE..]) as usize;
+        if offset_first % molecule::NUMBER_SIZE != 0 || offset_first < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        if slice_len < offset_first {
+            return ve!(Self, HeaderIsBroken, offset_first, slice_len);
+        }
+        let field_count = offset_first / molecule::NUMBER_SIZE - 1;
+        if field_count < Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        } else if !compatible && field_count > Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        };
+        let mut offsets: Vec<usize> = slice[molecule::NUMBER_SIZE..offset_first]
+            .chunks_exact(molecule::NUMBER_SIZE)
+            .map(|x| molecule::unpack_number(x) as usize)
+            .collect();
+        offsets.push(total_size);
+        if offsets.windows(2).any(|i| i[0] > i[1]) {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        BytesReader::verify(&slice[offsets[0]..offsets[1]], compatible)?;
+        BuyerCountVecReader::verify(&slice[offsets[1]..offsets[2]], compatible)?;
+        Ok(())
+    }
+}
+#[derive(Clone, Debug, Default)]
+pub struct AllowlistWitnessBuilder {
+    pub(crate) proof: Bytes,
+    pub(crate) buyers: BuyerCountVec,
+}
+impl AllowlistWitnessBuilder {
+    pub const FIELD_COUNT: usize = 2;
+    pub fn proof(mut self, v: Bytes) -> Self {
+        self.proof = v;
+        self
+    }
+    pub fn buyers(mut self, v: BuyerCountVec) -> Self {
+        self.buyers = v;
+        self
+    }
+}
+impl molecule::prelude::Builder for AllowlistWitnessBuilder {
+    type Entity = AllowlistWitness;
+    const NAME: &'static str = "AllowlistWitnessBuilder";
+    fn expected_length(&self) -> usize {
+        molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1)
+            + self.proof.as_slice().len()
+            + self.buyers.as_slice().len()
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        let mut total_size = molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1);
+        let mut offsets = Vec::with_capacity(Self::FIELD_COUNT);
+        offsets.push(total_size);
+        total_size += self.proof.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.buyers.as_slice().len();
+        writer.write_all(&molecule::pack_number(total_size as molecule::Number))?;
+        for offset in offsets.into_iter() {
+            writer.write_all(&molecule::pack_number(offset as molecule::Number))?;
+        }
+        writer.write_all(self.proof.as_slice())?;
+        writer.write_all(self.buyers.as_slice())?;
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        AllowlistWitness::new_unchecked(inner.into())
+    }
+}
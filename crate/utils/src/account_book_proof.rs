@@ -2,17 +2,15 @@ extern crate alloc;
 
 use crate::{Hash, Level};
 use alloc::vec::Vec;
-use ckb_std::ckb_types::prelude::Unpack;
+use ckb_std::ckb_types::prelude::{Builder, Entity, Pack, Unpack};
 use ckb_std::log::{self};
 pub use sparse_merkle_tree::traits::Value;
 pub use sparse_merkle_tree::{blake2b::Blake2bHasher, CompiledMerkleProof, H256};
 use types::error::SilentBerryError as Error;
-use types::AccountBookData;
+use types::{AccountBookData, MemberLeaf};
 
-#[cfg(feature = "std")]
 use sparse_merkle_tree::{default_store::DefaultStore, SparseMerkleTree};
 
-#[cfg(feature = "std")]
 pub type SMTTree = SparseMerkleTree<Blake2bHasher, SmtValue, DefaultStore<SmtValue>>;
 
 #[derive(Clone)]
@@ -24,9 +22,12 @@ pub enum SmtKey {
     Platform,
     Auther,
     Member(crate::Hash),
+    /// The lock hash of a buyer in the allowlist.
+    Buyer(crate::Hash),
 }
 impl SmtKey {
     pub fn get_key(&self) -> H256 {
+        let buyer;
         crate::Hash::ckb_hash(match self {
             Self::TotalA => "Total-A".as_bytes(),
             Self::TotalB => "Total-B".as_bytes(),
@@ -35,27 +36,44 @@ impl SmtKey {
             Self::Platform => "Platform".as_bytes(),
             Self::Auther => "Auther".as_bytes(),
             Self::Member(hash) => hash.as_slice(),
+            Self::Buyer(hash) => {
+                buyer = [b"Buyer".as_slice(), hash.as_slice()].concat();
+                &buyer
+            }
         })
         .into()
     }
 }
 
-#[derive(Default, Clone)]
-pub struct SmtValue {
-    pub amount: u128,
+/// Value of the AccountBook SMT: the totals, Platform and Auther are amounts,
+/// members are `MemberLeaf`.
+#[derive(Clone)]
+pub enum SmtValue {
+    Amount(u128),
+    Member(MemberLeaf),
+}
+impl Default for SmtValue {
+    fn default() -> Self {
+        Self::Amount(0)
+    }
 }
 impl Value for SmtValue {
     fn to_h256(&self) -> H256 {
-        let mut hasher = blake2b_ref::Blake2bBuilder::new(crate::HASH_SIZE)
-            .personal(crate::hash::CKB_HASH_PERSONALIZATION)
-            .build();
+        match self {
+            Self::Amount(amount) => {
+                let mut hasher = blake2b_ref::Blake2bBuilder::new(crate::HASH_SIZE)
+                    .personal(crate::hash::CKB_HASH_PERSONALIZATION)
+                    .build();
 
-        hasher.update(&self.amount.to_le_bytes());
+                hasher.update(&amount.to_le_bytes());
 
-        let mut hash = [0u8; 32];
-        hasher.finalize(&mut hash);
+                let mut hash = [0u8; 32];
+                hasher.finalize(&mut hash);
 
-        hash.into()
+                hash.into()
+            }
+            Self::Member(leaf) => Hash::ckb_hash(leaf.as_slice()).into(),
+        }
     }
     fn zero() -> Self {
         Default::default()
@@ -63,52 +81,166 @@ impl Value for SmtValue {
 }
 impl SmtValue {
     pub fn new(a: u128) -> Self {
-        Self { amount: a }
+        Self::Amount(a)
+    }
+    pub fn new_member(level: Level, withdrawn: u128, joined_at: u32, share_index: u128) -> Self {
+        Self::Member(
+            MemberLeaf::new_builder()
+                .level((level as u8).into())
+                .withdrawn(withdrawn.pack())
+                .joined_at(joined_at.pack())
+                .share_index(share_index.pack())
+                .build(),
+        )
+    }
+
+    /// The amount, for a member it is the cumulative withdrawn amount.
+    pub fn amount(&self) -> u128 {
+        match self {
+            Self::Amount(amount) => *amount,
+            Self::Member(leaf) => leaf.withdrawn().unpack(),
+        }
+    }
+    /// The index of the member level when a member joined, 0 for an amount.
+    pub fn share_index(&self) -> u128 {
+        match self {
+            Self::Amount(_) => 0,
+            Self::Member(leaf) => leaf.share_index().unpack(),
+        }
+    }
+    pub fn with_amount(&self, amount: u128) -> Self {
+        match self {
+            Self::Amount(_) => Self::Amount(amount),
+            Self::Member(leaf) => {
+                Self::Member(leaf.clone().as_builder().withdrawn(amount.pack()).build())
+            }
+        }
+    }
+}
+
+/// Root hash of a newly created AccountBook: the four totals and the
+/// Platform / Auther leaves are present and set to 0.
+pub fn genesis_root() -> Result<Hash, Error> {
+    let mut tree = SMTTree::default();
+    for key in [
+        SmtKey::TotalA,
+        SmtKey::TotalB,
+        SmtKey::TotalC,
+        SmtKey::TotalD,
+        SmtKey::Platform,
+        SmtKey::Auther,
+    ] {
+        tree.update(key.get_key(), SmtValue::new(0)).map_err(|e| {
+            log::error!("Update genesis Smt Error: {:?}", e);
+            Error::Smt
+        })?;
+    }
+    Ok((*tree.root()).into())
+}
+
+/// A leaf of the AccountBook SMT with its value before and after the transaction,
+/// `None` is an empty leaf.
+#[derive(Clone)]
+pub struct SmtLeaf {
+    pub key: SmtKey,
+    pub old: Option<SmtValue>,
+    pub new: Option<SmtValue>,
+}
+impl SmtLeaf {
+    pub fn new(key: SmtKey, old: Option<SmtValue>, new: Option<SmtValue>) -> Self {
+        Self { key, old, new }
+    }
+    pub fn unchanged(key: SmtKey, value: Option<SmtValue>) -> Self {
+        Self::new(key, value.clone(), value)
+    }
+
+    fn to_h256(key: &SmtKey, value: &Option<SmtValue>) -> (H256, H256) {
+        (
+            key.get_key(),
+            value.as_ref().map_or_else(H256::zero, |v| v.to_h256()),
+        )
     }
 }
 
 pub struct AccountBookProof {
-    proof: Vec<u8>,
+    proof: CompiledMerkleProof,
 }
 impl AccountBookProof {
     pub fn new(proof: Vec<u8>) -> Self {
-        Self { proof }
-    }
-
-    pub fn verify(
-        &self,
-        root: Hash,
-        total: TotalAmounts,
-        member: (SmtKey, Option<u128>),
-    ) -> Result<bool, Error> {
-        use alloc::vec;
-        let proof = CompiledMerkleProof(self.proof.clone());
-
-        proof
-            .verify::<Blake2bHasher>(
-                &root.into(),
-                vec![
-                    (SmtKey::TotalA.get_key(), SmtValue::new(total.a).to_h256()),
-                    (SmtKey::TotalB.get_key(), SmtValue::new(total.b).to_h256()),
-                    (SmtKey::TotalC.get_key(), SmtValue::new(total.c).to_h256()),
-                    (SmtKey::TotalD.get_key(), SmtValue::new(total.d).to_h256()),
-                    (
-                        member.0.get_key(),
-                        if let Some(a) = member.1 {
-                            SmtValue::new(a).to_h256()
-                        } else {
-                            Default::default()
-                        },
-                    ),
-                ],
-            )
+        Self {
+            proof: CompiledMerkleProof(proof),
+        }
+    }
+
+    /// Verifies the old values of `leaves` against `old_root` and the new values against `new_root`.
+    pub fn verify(&self, old_root: Hash, new_root: Hash, leaves: &[SmtLeaf]) -> Result<(), Error> {
+        let old_leaves = leaves
+            .iter()
+            .map(|leaf| SmtLeaf::to_h256(&leaf.key, &leaf.old))
+            .collect();
+        if !self.verify_root(old_root, old_leaves)? {
+            log::error!("Verify Input SMT failed");
+            return Err(Error::Smt);
+        }
+
+        let new_leaves = leaves
+            .iter()
+            .map(|leaf| SmtLeaf::to_h256(&leaf.key, &leaf.new))
+            .collect();
+        if !self.verify_root(new_root, new_leaves)? {
+            log::error!("Verify Output SMT failed");
+            return Err(Error::Smt);
+        }
+
+        Ok(())
+    }
+
+    fn verify_root(&self, root: Hash, leaves: Vec<(H256, H256)>) -> Result<bool, Error> {
+        self.proof
+            .verify::<Blake2bHasher>(&root.into(), leaves)
             .map_err(|e| {
-                ckb_std::log::error!("Verify Inputs Smt Error: {:?}", e);
+                log::error!("Verify Smt Error: {:?}", e);
                 Error::Smt
             })
     }
 }
 
+/// Proof of buyers in the allowlist, a SMT of `SmtKey::Buyer` with the value 1.
+pub struct AllowlistProof {
+    proof: CompiledMerkleProof,
+}
+impl AllowlistProof {
+    pub fn new(proof: Vec<u8>) -> Self {
+        Self {
+            proof: CompiledMerkleProof(proof),
+        }
+    }
+
+    pub fn verify(&self, root: Hash, buyers: &[Hash]) -> Result<(), Error> {
+        let leaves = buyers
+            .iter()
+            .map(|buyer| {
+                (
+                    SmtKey::Buyer(buyer.clone()).get_key(),
+                    SmtValue::new(1).to_h256(),
+                )
+            })
+            .collect();
+        let verified = self
+            .proof
+            .verify::<Blake2bHasher>(&root.into(), leaves)
+            .map_err(|e| {
+                log::error!("Verify allowlist Smt Error: {:?}", e);
+                Error::Smt
+            })?;
+        if !verified {
+            log::error!("Buyers are not in the allowlist");
+            return Err(Error::Allowlist);
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone)]
 pub struct TotalAmounts {
     pub a: u128,
@@ -117,8 +249,39 @@ pub struct TotalAmounts {
     pub d: u128,
 }
 impl TotalAmounts {
-    pub fn total(&self) -> u128 {
-        self.a + self.b + self.c + self.d
+    pub fn total(&self) -> Result<u128, Error> {
+        [self.a, self.b, self.c, self.d]
+            .into_iter()
+            .try_fold(0u128, |sum, v| sum.checked_add(v))
+            .ok_or_else(|| {
+                log::error!("Sum of totals overflow");
+                Error::AccountBookOverflow
+            })
+    }
+    /// The four total leaves, changing from `self` to `new`.
+    pub fn leaves(&self, new: &TotalAmounts) -> [SmtLeaf; 4] {
+        [
+            SmtLeaf::new(
+                SmtKey::TotalA,
+                Some(SmtValue::new(self.a)),
+                Some(SmtValue::new(new.a)),
+            ),
+            SmtLeaf::new(
+                SmtKey::TotalB,
+                Some(SmtValue::new(self.b)),
+                Some(SmtValue::new(new.b)),
+            ),
+            SmtLeaf::new(
+                SmtKey::TotalC,
+                Some(SmtValue::new(self.c)),
+                Some(SmtValue::new(new.c)),
+            ),
+            SmtLeaf::new(
+                SmtKey::TotalD,
+                Some(SmtValue::new(self.d)),
+                Some(SmtValue::new(new.d)),
+            ),
+        ]
     }
     pub fn add(&mut self, v: u128, level: Level) -> Result<(), Error> {
         match level {
@@ -165,12 +328,3 @@ impl From<&AccountBookData> for TotalAmounts {
         }
     }
 }
-
-// pub fn get_withdrawal(
-//     level: u8,
-//     totals: TotalAmounts,
-//     data: types::AccountBookData,
-//     cell_data: types::AccountBookCellData,
-// ) -> u128 {
-//     0
-// }
@@ -0,0 +1,208 @@
+use alloc::vec::Vec;
+use ckb_std::log;
+use types::error::SilentBerryError as Error;
+
+pub const DOB_CONTENT_TYPE: &[u8] = b"dob/1";
+
+// Nesting limit of the JSON values skipped while looking for the dna
+const MAX_DEPTH: usize = 16;
+
+/// Decode the DNA of a `dob/1` spore content. The content is either
+/// `{"dna": "<hex>", ...}`, `"<hex>"` or `["<hex>", ...]`.
+pub fn decode_dna(content: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut parser = Parser {
+        data: content,
+        pos: 0,
+    };
+    let dna = parser.parse_dna()?;
+    parser.skip_ws();
+    if parser.pos != content.len() {
+        log::error!("DOB content has trailing data at {}", parser.pos);
+        return Err(Error::Spore);
+    }
+
+    decode_hex(dna)
+}
+
+fn decode_hex(hex: &[u8]) -> Result<Vec<u8>, Error> {
+    if hex.is_empty() || hex.len() % 2 != 0 {
+        log::error!("DNA hex length error: {}", hex.len());
+        return Err(Error::Spore);
+    }
+    let nibble = |c: u8| -> Result<u8, Error> {
+        (c as char).to_digit(16).map(|v| v as u8).ok_or_else(|| {
+            log::error!("DNA is not a hex string");
+            Error::Spore
+        })
+    };
+    hex.chunks(2)
+        .map(|c| Ok((nibble(c[0])? << 4) | nibble(c[1])?))
+        .collect()
+}
+
+struct Parser<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn parse_dna(&mut self) -> Result<&'a [u8], Error> {
+        self.skip_ws();
+        match self.peek()? {
+            b'"' => self.parse_string(),
+            b'[' => {
+                self.expect(b'[')?;
+                self.skip_ws();
+                let dna = self.parse_string()?;
+                self.skip_ws();
+                while self.peek()? == b',' {
+                    self.expect(b',')?;
+                    self.skip_value(1)?;
+                    self.skip_ws();
+                }
+                self.expect(b']')?;
+                Ok(dna)
+            }
+            b'{' => {
+                self.expect(b'{')?;
+                let mut dna = None;
+                loop {
+                    self.skip_ws();
+                    let key = self.parse_string()?;
+                    self.skip_ws();
+                    self.expect(b':')?;
+                    self.skip_ws();
+                    if key == b"dna" {
+                        if dna.is_some() {
+                            log::error!("DOB content has duplicate dna");
+                            return Err(Error::Spore);
+                        }
+                        dna = Some(self.parse_string()?);
+                    } else {
+                        self.skip_value(1)?;
+                    }
+                    self.skip_ws();
+                    match self.next()? {
+                        b',' => continue,
+                        b'}' => break,
+                        c => return Err(self.unexpected(c)),
+                    }
+                }
+                dna.ok_or_else(|| {
+                    log::error!("DOB content has no dna");
+                    Error::Spore
+                })
+            }
+            c => Err(self.unexpected(c)),
+        }
+    }
+
+    // Returns the raw bytes between the quotes, escapes are skipped but not decoded.
+    fn parse_string(&mut self) -> Result<&'a [u8], Error> {
+        self.expect(b'"')?;
+        let begin = self.pos;
+        loop {
+            match self.next()? {
+                b'"' => return Ok(&self.data[begin..self.pos - 1]),
+                b'\\' => {
+                    self.next()?;
+                }
+                c if c < 0x20 => return Err(self.unexpected(c)),
+                _ => {}
+            }
+        }
+    }
+
+    fn skip_value(&mut self, depth: usize) -> Result<(), Error> {
+        if depth > MAX_DEPTH {
+            log::error!("DOB content nests too deep");
+            return Err(Error::Spore);
+        }
+        self.skip_ws();
+        match self.peek()? {
+            b'"' => {
+                self.parse_string()?;
+            }
+            open @ (b'{' | b'[') => {
+                let close = if open == b'{' { b'}' } else { b']' };
+                self.expect(open)?;
+                self.skip_ws();
+                if self.peek()? == close {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                loop {
+                    if open == b'{' {
+                        self.skip_ws();
+                        self.parse_string()?;
+                        self.skip_ws();
+                        self.expect(b':')?;
+                    }
+                    self.skip_value(depth + 1)?;
+                    self.skip_ws();
+                    match self.next()? {
+                        b',' => continue,
+                        c if c == close => break,
+                        c => return Err(self.unexpected(c)),
+                    }
+                }
+            }
+            _ => {
+                // number, true, false or null
+                let begin = self.pos;
+                while self
+                    .data
+                    .get(self.pos)
+                    .is_some_and(|c| c.is_ascii_alphanumeric() || b"+-.".contains(c))
+                {
+                    self.pos += 1;
+                }
+                if begin == self.pos {
+                    let c = self.data[self.pos];
+                    return Err(self.unexpected(c));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn skip_ws(&mut self) {
+        while self
+            .data
+            .get(self.pos)
+            .is_some_and(|c| matches!(c, b' ' | b'\t' | b'\n' | b'\r'))
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&self) -> Result<u8, Error> {
+        self.data.get(self.pos).copied().ok_or_else(|| {
+            log::error!("DOB content ends unexpectedly");
+            Error::Spore
+        })
+    }
+
+    fn next(&mut self) -> Result<u8, Error> {
+        let c = self.peek()?;
+        self.pos += 1;
+        Ok(c)
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), Error> {
+        let c = self.next()?;
+        if c != expected {
+            return Err(self.unexpected(c));
+        }
+        Ok(())
+    }
+
+    fn unexpected(&self, c: u8) -> Error {
+        log::error!(
+            "DOB content format error, unexpected {:?} at {}",
+            c as char,
+            self.pos
+        );
+        Error::Spore
+    }
+}
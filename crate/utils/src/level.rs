@@ -3,7 +3,7 @@ use spore_types::spore::SporeData;
 use types::error::SilentBerryError as Error;
 
 #[repr(u8)]
-#[derive(PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Level {
     A = 1,
     B,
@@ -12,30 +12,39 @@ pub enum Level {
     Platform = 0x11,
     Auther = 0x12,
 }
-impl TryFrom<SporeData> for Level {
-    type Error = Error;
-    fn try_from(data: SporeData) -> Result<Self, Self::Error> {
-        use alloc::string::String;
-        let content = String::from_utf8(data.content().raw_data().to_vec()).map_err(|e| {
-            log::error!("Parse Spore Content to string Failed, error: {:?}", e);
+impl Level {
+    /// Level of a `dob/1` spore, the `width` bytes of its DNA at `offset` as a big endian number.
+    pub fn from_spore(data: &SporeData, offset: u8, width: u8) -> Result<Self, Error> {
+        if data.content_type().raw_data().as_ref() != crate::dob::DOB_CONTENT_TYPE {
+            log::error!("Spore content type is not dob/1");
+            return Err(Error::Spore);
+        }
+        let dna = crate::dob::decode_dna(&data.content().raw_data())?;
+        Self::from_dna(&dna, offset, width)
+    }
+
+    pub fn from_dna(dna: &[u8], offset: u8, width: u8) -> Result<Self, Error> {
+        let (offset, width) = (offset as usize, width as usize);
+        if width == 0 || width > 8 {
+            log::error!("DNA level width should be 1~8, but it is {}", width);
+            return Err(Error::Spore);
+        }
+        let bytes = dna.get(offset..offset + width).ok_or_else(|| {
+            log::error!(
+                "DNA too short, len: {}, level offset: {}, width: {}",
+                dna.len(),
+                offset,
+                width
+            );
             Error::Spore
         })?;
-
-        let v = content
-            .chars()
-            .rev()
-            .find(|c| c.is_ascii_hexdigit())
-            .ok_or_else(|| {
-                log::error!("Spore Content format error, unable to find level");
+        let value = bytes.iter().fold(0u64, |v, b| (v << 8) | *b as u64);
+        u8::try_from(value)
+            .map_err(|_| {
+                log::error!("Spore level error, the value is {}", value);
                 Error::Spore
             })?
-            .to_digit(16)
-            .ok_or_else(|| {
-                log::error!("Spore Content format error, unable to find level");
-                Error::Spore
-            })? as u8;
-
-        v.try_into()
+            .try_into()
     }
 }
 impl TryFrom<ckb_std::ckb_types::packed::Byte> for Level {
@@ -4,12 +4,19 @@ extern crate alloc;
 
 #[cfg(feature = "smt")]
 pub mod account_book_proof;
+#[cfg(feature = "smt")]
+pub mod profit;
 
 mod hash;
 pub use hash::{Hash, HASH_SIZE};
 
 mod udt_info;
-pub use udt_info::UDTInfo;
+pub use udt_info::{load_udt_amount, Asset, UDTData, UDTInfo, MAX_CKB_FEE};
+
+mod since;
+pub use since::Since;
+
+pub mod dob;
 
 mod level;
 pub use level::*;
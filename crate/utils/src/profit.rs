@@ -0,0 +1,229 @@
+//! Profit distribution of the AccountBook.
+//!
+//! Each level's profit array splits the income of that level by columns
+//! `[Platform, Auther, A, B, C]` (truncated to the array length). The Auther
+//! takes its column whole. A member column is shared by the members of that
+//! level through a per-share index: each selling adds its income to the pool
+//! of the level and divides the pool evenly among the members counted after
+//! the selling, raising the index. A member is entitled to the rise of the
+//! index since it joined, so a share never shrinks when others join, and the
+//! remainder of the division stays in the pool for the next selling. Once no
+//! member of the level can still arrive (it is sold out, or closed by lowering
+//! its quota) the pool is left to the Platform, and the sellings after only
+//! divide their own income. The Platform takes its column plus the rounding
+//! remainders of the other columns and the pools left to it, so the
+//! entitlements of all parties never exceed the totals.
+
+use crate::{account_book_proof::TotalAmounts, Level};
+use ckb_std::{
+    ckb_types::prelude::{Builder, Entity, Pack, Unpack},
+    log,
+};
+use types::{error::SilentBerryError as Error, AccountBookCellData, LevelShare};
+
+const COLUMNS: usize = 5;
+const AUTHER_COLUMN: usize = 1;
+
+/// Per-share state of a member level, see `LevelShare`.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct Share {
+    pub index: u128,
+    /// Income not divided among the members yet, in hundredths.
+    pub pool: u128,
+}
+impl From<LevelShare> for Share {
+    fn from(value: LevelShare) -> Self {
+        Self {
+            index: value.index().unpack(),
+            pool: value.pool().unpack(),
+        }
+    }
+}
+impl From<Share> for LevelShare {
+    fn from(value: Share) -> Self {
+        LevelShare::new_builder()
+            .index(value.index.pack())
+            .pool(value.pool.pack())
+            .build()
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct ProfitConfig {
+    /// Percentages of the levels A~D by column, missing columns are 0.
+    pub profits: [[u8; COLUMNS]; 4],
+    /// Members of the levels A~C (`a_count`, `b_count`, `c_count`).
+    pub counts: [u32; 3],
+    /// Quotas of the levels A~C (`a_num`, `b_num`, `c_num`).
+    pub nums: [u32; 3],
+    /// Per-share state of the levels A~C.
+    pub shares: [Share; 3],
+}
+
+impl From<&AccountBookCellData> for ProfitConfig {
+    fn from(value: &AccountBookCellData) -> Self {
+        let mut profits = [[0u8; COLUMNS]; 4];
+        for (profit, data) in profits.iter_mut().zip([
+            value.a_profit().raw_data(),
+            value.b_profit().raw_data(),
+            value.c_profit().raw_data(),
+            value.d_profit().raw_data(),
+        ]) {
+            profit[..data.len()].copy_from_slice(&data);
+        }
+
+        Self {
+            profits,
+            counts: [
+                value.a_count().unpack(),
+                value.b_count().unpack(),
+                value.c_count().unpack(),
+            ],
+            nums: [
+                value.a_num().unpack(),
+                value.b_num().unpack(),
+                value.c_num().unpack(),
+            ],
+            shares: [
+                value.a_share().into(),
+                value.b_share().into(),
+                value.c_share().into(),
+            ],
+        }
+    }
+}
+
+// Index of a member level in `counts` and `shares`, `None` for level D which has no column.
+fn member_index(level: Level) -> Result<Option<usize>, Error> {
+    match level {
+        Level::A => Ok(Some(0)),
+        Level::B => Ok(Some(1)),
+        Level::C => Ok(Some(2)),
+        Level::D => Ok(None),
+        _ => {
+            log::error!("{:?} is not a member level", level);
+            Err(Error::Spore)
+        }
+    }
+}
+
+/// The index a member of `level` joining now starts from.
+pub fn share_index(shares: &[Share; 3], level: Level) -> Result<u128, Error> {
+    Ok(member_index(level)?.map_or(0, |index| shares[index].index))
+}
+
+fn overflow() -> Error {
+    log::error!("Calculate profit overflow");
+    Error::AccountBookOverflow
+}
+
+impl ProfitConfig {
+    /// Income of a column in hundredths: `sum(total * percent)`.
+    fn column_amount(&self, totals: &TotalAmounts, column: usize) -> Result<u128, Error> {
+        let mut amount = 0u128;
+        for (total, profit) in [totals.a, totals.b, totals.c, totals.d]
+            .into_iter()
+            .zip(self.profits)
+        {
+            amount = total
+                .checked_mul(profit[column] as u128)
+                .and_then(|v| v.checked_add(amount))
+                .ok_or_else(overflow)?;
+        }
+        Ok(amount)
+    }
+
+    /// Whether no member of the member level `index` (0~2 for A~C) can still arrive.
+    pub fn is_closed(&self, index: usize) -> bool {
+        self.counts[index] >= self.nums[index]
+    }
+
+    /// Shares after a selling that raises the totals from `old_totals` to `new_totals`, from
+    /// the `old` config before the selling to `self.counts` members after it.
+    pub fn next_shares(
+        &self,
+        old: &ProfitConfig,
+        old_totals: &TotalAmounts,
+        new_totals: &TotalAmounts,
+    ) -> Result<[Share; 3], Error> {
+        let mut next = old.shares;
+        for (index, (share, count)) in next.iter_mut().zip(self.counts).enumerate() {
+            let income = self
+                .column_amount(new_totals, index + 2)?
+                .checked_sub(self.column_amount(old_totals, index + 2)?)
+                .ok_or_else(overflow)?;
+            let left = if old.is_closed(index) { share.pool } else { 0 };
+            let mut pool = (share.pool - left)
+                .checked_add(income)
+                .ok_or_else(overflow)?;
+            if count != 0 {
+                let divisor = 100 * count as u128;
+                let amount = pool / divisor;
+                share.index = share.index.checked_add(amount).ok_or_else(overflow)?;
+                pool -= amount * divisor;
+            }
+            share.pool = left + pool;
+        }
+        Ok(next)
+    }
+
+    /// Amount a member of `level` that joined at `share_index` is entitled to.
+    pub fn member(&self, level: Level, share_index: u128) -> Result<u128, Error> {
+        let index = match member_index(level)? {
+            Some(index) => index,
+            None => return Ok(0),
+        };
+        self.shares[index]
+            .index
+            .checked_sub(share_index)
+            .ok_or_else(|| {
+                log::error!(
+                    "The member of level {:?} joined after the index {}",
+                    level,
+                    self.shares[index].index
+                );
+                Error::AccountBookOverflow
+            })
+    }
+
+    /// Amount the Auther is entitled to.
+    pub fn auther(&self, totals: &TotalAmounts) -> Result<u128, Error> {
+        Ok(self.column_amount(totals, AUTHER_COLUMN)? / 100)
+    }
+
+    /// Amount the Platform is entitled to: everything that is not paid to
+    /// the Auther or reserved for the members, including the rounding remainders
+    /// and the pools of the closed levels.
+    pub fn platform(&self, totals: &TotalAmounts) -> Result<u128, Error> {
+        let mut others = self.auther(totals)?;
+        for index in 0..self.shares.len() {
+            let mut amount = self.column_amount(totals, index + 2)?;
+            if self.is_closed(index) {
+                amount = amount
+                    .checked_sub(self.shares[index].pool)
+                    .ok_or_else(overflow)?;
+            }
+            others = others.checked_add(amount / 100).ok_or_else(overflow)?;
+        }
+
+        totals.total()?.checked_sub(others).ok_or_else(|| {
+            log::error!("Profit exceeds the totals, the sum of profit must be 100");
+            Error::AccountBookOverflow
+        })
+    }
+
+    /// Total amount `level` (a member level, the Platform or the Auther) is entitled to
+    /// withdraw, `share_index` is the index a member joined at.
+    pub fn get_withdrawal(
+        &self,
+        level: Level,
+        share_index: u128,
+        totals: &TotalAmounts,
+    ) -> Result<u128, Error> {
+        match level {
+            Level::Platform => self.platform(totals),
+            Level::Auther => self.auther(totals),
+            _ => self.member(level, share_index),
+        }
+    }
+}
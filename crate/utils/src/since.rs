@@ -0,0 +1,103 @@
+use ckb_std::{
+    ckb_constants::Source,
+    ckb_types::prelude::Unpack,
+    high_level::load_input_since,
+    log,
+    since::{LockValue, Since as RawSince},
+};
+use types::error::SilentBerryError as Error;
+
+const METRIC_MASK: u64 = 0x6000_0000_0000_0000;
+const TIMESTAMP_METRIC: u64 = 0x4000_0000_0000_0000;
+const VALUE_MASK: u64 = 0x00ff_ffff_ffff_ffff;
+
+/// A `since` value whose flags are valid, see RFC 0017.
+#[derive(Clone, Copy, Debug)]
+pub struct Since(RawSince);
+
+impl TryFrom<u64> for Since {
+    type Error = Error;
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        let since = RawSince::new(value);
+        if !since.flags_is_valid() {
+            log::error!("Since flags are invalid: {:#x}", value);
+            return Err(Error::ExpireSince);
+        }
+
+        // `extract_lock_value` gives the timestamp in milliseconds, which must fit in u64
+        if value & METRIC_MASK == TIMESTAMP_METRIC && value & VALUE_MASK > u64::MAX / 1000 {
+            log::error!("Since timestamp is too large: {:#x}", value);
+            return Err(Error::ExpireSince);
+        }
+
+        if let Some(LockValue::EpochNumberWithFraction(_)) = since.extract_lock_value() {
+            // `EpochNumberWithFraction` treats a zero length as a whole epoch, so check the raw fields
+            let index = (value >> 24) & 0xffff;
+            let length = (value >> 40) & 0xffff;
+            if (length == 0 && index != 0) || (length != 0 && index >= length) {
+                log::error!(
+                    "Since epoch is invalid, index: {}, length: {}",
+                    index,
+                    length
+                );
+                return Err(Error::ExpireSince);
+            }
+        }
+
+        Ok(Self(since))
+    }
+}
+
+impl TryFrom<types::blockchain::Uint64> for Since {
+    type Error = Error;
+    fn try_from(value: types::blockchain::Uint64) -> Result<Self, Self::Error> {
+        let value: u64 = value.unpack();
+        value.try_into()
+    }
+}
+
+impl Since {
+    pub fn load_input(index: usize, source: Source) -> Result<Self, Error> {
+        load_input_since(index, source)?.try_into()
+    }
+
+    pub fn as_u64(&self) -> u64 {
+        self.0.as_u64()
+    }
+
+    pub fn is_absolute(&self) -> bool {
+        self.0.is_absolute()
+    }
+
+    /// Whether `self` is at or after `other`. Both must be absolute or relative, and in the
+    /// same metric, otherwise they can not be compared.
+    pub fn reaches(&self, other: &Since) -> Result<bool, Error> {
+        if self.0.is_absolute() != other.0.is_absolute() {
+            log::error!(
+                "Since can not be compared, absolute and relative: {:#x}, {:#x}",
+                self.as_u64(),
+                other.as_u64()
+            );
+            return Err(Error::ExpireSince);
+        }
+
+        // Flags are checked when created
+        let value = self.0.extract_lock_value().ok_or(Error::ExpireSince)?;
+        let other_value = other.0.extract_lock_value().ok_or(Error::ExpireSince)?;
+        match (value, other_value) {
+            (LockValue::BlockNumber(a), LockValue::BlockNumber(b)) => Ok(a >= b),
+            (LockValue::EpochNumberWithFraction(a), LockValue::EpochNumberWithFraction(b)) => {
+                Ok(a >= b)
+            }
+            (LockValue::Timestamp(a), LockValue::Timestamp(b)) => Ok(a >= b),
+            _ => {
+                log::error!(
+                    "Since can not be compared, metrics are different: {:#x}, {:#x}",
+                    self.as_u64(),
+                    other.as_u64()
+                );
+                Err(Error::ExpireSince)
+            }
+        }
+    }
+}
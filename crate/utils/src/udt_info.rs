@@ -3,40 +3,221 @@ use alloc::vec::Vec;
 use ckb_std::{
     ckb_constants::Source,
     error::SysError,
-    high_level::{load_cell_data, load_cell_type_hash},
+    high_level::{
+        load_cell_capacity, load_cell_data, load_cell_lock_hash, load_cell_occupied_capacity,
+        load_cell_type_hash,
+    },
     log,
 };
 use types::error::SilentBerryError;
 
+/// Data of a xUDT cell: the amount in the first 16 bytes, then the extension data.
+pub struct UDTData {
+    pub amount: u128,
+    pub extension: Vec<u8>,
+}
+impl UDTData {
+    pub fn load(index: usize, source: Source) -> Result<Self, SilentBerryError> {
+        let mut data = load_cell_data(index, source)?;
+        if data.len() < 16 {
+            log::error!("Parse {:?} xudt data failed: {:02x?}", source, data);
+            return Err(SilentBerryError::CheckXUDT);
+        }
+        let extension = data.split_off(16);
+        let mut amount = [0u8; 16];
+        amount.copy_from_slice(&data);
+        Ok(Self {
+            amount: u128::from_le_bytes(amount),
+            extension,
+        })
+    }
+}
+
+pub fn load_udt_amount(index: usize, source: Source) -> Result<u128, SilentBerryError> {
+    Ok(UDTData::load(index, source)?.amount)
+}
+
+/// The most a receiver of CKB may spend on the transaction fee out of its payment, 1 CKB.
+/// Only allowed to a receiver signing the transaction, otherwise the submitter could keep it.
+pub const MAX_CKB_FEE: u128 = 1_0000_0000;
+
+/// The asset a sale is paid in, an all-zero xUDT script hash stands for native CKB.
+#[derive(Clone, PartialEq, Eq)]
+pub enum Asset {
+    XUDT(Hash),
+    CKB,
+}
+impl From<Hash> for Asset {
+    fn from(value: Hash) -> Self {
+        if value == [0u8; 32] {
+            Self::CKB
+        } else {
+            Self::XUDT(value)
+        }
+    }
+}
+impl From<types::blockchain::Byte32> for Asset {
+    fn from(value: types::blockchain::Byte32) -> Self {
+        Hash::from(value).into()
+    }
+}
+impl Asset {
+    /// Whether a cell with this type script holds the asset. CKB is only held by cells
+    /// without type script.
+    pub fn is_held_by(&self, type_hash: &Option<[u8; 32]>) -> bool {
+        match self {
+            Self::XUDT(hash) => *hash == *type_hash,
+            Self::CKB => type_hash.is_none(),
+        }
+    }
+
+    /// The most of a payment the receiver may spend on the transaction fee: [`MAX_CKB_FEE`]
+    /// in CKB, none in xUDT.
+    pub fn max_fee(&self) -> u128 {
+        match self {
+            Self::XUDT(_) => 0,
+            Self::CKB => MAX_CKB_FEE,
+        }
+    }
+
+    /// Amount of the asset in the cell, `None` if the cell does not hold it.
+    pub fn load_amount(
+        &self,
+        index: usize,
+        source: Source,
+    ) -> Result<Option<u128>, SilentBerryError> {
+        let type_hash = load_cell_type_hash(index, source)?;
+        if !self.is_held_by(&type_hash) {
+            return Ok(None);
+        }
+        self.load_held_amount(index, source).map(Some)
+    }
+
+    // The CKB amount is the capacity beyond what the cell occupies, so the cell itself is
+    // never counted as paid.
+    fn load_held_amount(&self, index: usize, source: Source) -> Result<u128, SilentBerryError> {
+        match self {
+            Self::XUDT(_) => load_udt_amount(index, source),
+            Self::CKB => {
+                let capacity = load_cell_capacity(index, source)?;
+                let occupied = load_cell_occupied_capacity(index, source)?;
+                let amount = capacity.checked_sub(occupied).ok_or_else(|| {
+                    log::error!(
+                        "{:?} cell {} capacity: {} is less than occupied: {}",
+                        source,
+                        index,
+                        capacity,
+                        occupied
+                    );
+                    SilentBerryError::CapacityError
+                })?;
+                Ok(amount as u128)
+            }
+        }
+    }
+}
+
+pub struct UDTCell {
+    pub amount: u128,
+    pub index: usize,
+    pub lock_script_hash: Hash,
+}
+
+/// xUDT of one lock in the transaction.
+#[derive(Clone)]
+pub struct UDTFlow {
+    pub lock_script_hash: Hash,
+    pub input: u128,
+    pub output: u128,
+}
+impl UDTFlow {
+    /// Net amount received by the lock, `None` if it lost xUDT.
+    pub fn gained(&self) -> Option<u128> {
+        self.output.checked_sub(self.input)
+    }
+
+    /// Whether the lock received `amount`, less at most `fee` it paid as the transaction fee.
+    pub fn received(&self, amount: u128, fee: u128) -> bool {
+        self.gained()
+            .is_some_and(|gained| gained.saturating_add(fee) >= amount)
+    }
+
+    /// Net amount spent by the lock, `None` if it received xUDT.
+    pub fn lost(&self) -> Option<u128> {
+        self.input.checked_sub(self.output)
+    }
+}
+
+/// The asset of each cell and each lock in the transaction. Despite the name it also
+/// covers [`Asset::CKB`].
 pub struct UDTInfo {
-    pub inputs: Vec<(u128, usize)>,
-    pub outputs: Vec<(u128, usize)>,
+    pub asset: Asset,
+    pub inputs: Vec<UDTCell>,
+    pub outputs: Vec<UDTCell>,
+    pub flows: Vec<UDTFlow>,
+    input_total: u128,
+    output_total: u128,
 }
 impl UDTInfo {
-    pub fn new(xudt_script_hash: Hash) -> Result<Self, SilentBerryError> {
-        let inputs = Self::load_udt(Source::Input, &xudt_script_hash)?;
-        let outputs = Self::load_udt(Source::Output, &xudt_script_hash)?;
+    pub fn new(asset: Asset) -> Result<Self, SilentBerryError> {
+        let inputs = Self::load_udt(Source::Input, &asset)?;
+        let outputs = Self::load_udt(Source::Output, &asset)?;
 
-        Ok(Self { inputs, outputs })
+        let mut flows: Vec<UDTFlow> = Vec::new();
+        let mut input_total = 0u128;
+        let mut output_total = 0u128;
+        for (cells, is_input) in [(&inputs, true), (&outputs, false)] {
+            for cell in cells {
+                let pos = match flows
+                    .iter()
+                    .position(|f| f.lock_script_hash == cell.lock_script_hash)
+                {
+                    Some(pos) => pos,
+                    None => {
+                        flows.push(UDTFlow {
+                            lock_script_hash: cell.lock_script_hash.clone(),
+                            input: 0,
+                            output: 0,
+                        });
+                        flows.len() - 1
+                    }
+                };
+                let (flow, total) = if is_input {
+                    (&mut flows[pos].input, &mut input_total)
+                } else {
+                    (&mut flows[pos].output, &mut output_total)
+                };
+                // The lock amount never exceeds the total, one check covers both
+                *total = total.checked_add(cell.amount).ok_or_else(|| {
+                    log::error!("UDT overflow");
+                    SilentBerryError::CheckXUDT
+                })?;
+                *flow += cell.amount;
+            }
+        }
+
+        Ok(Self {
+            asset,
+            inputs,
+            outputs,
+            flows,
+            input_total,
+            output_total,
+        })
     }
 
-    fn load_udt(
-        source: Source,
-        xudt_script_hash: &Hash,
-    ) -> Result<Vec<(u128, usize)>, SilentBerryError> {
+    fn load_udt(source: Source, asset: &Asset) -> Result<Vec<UDTCell>, SilentBerryError> {
         let mut xudt_info = Vec::new();
         let mut index = 0usize;
         loop {
             match load_cell_type_hash(index, source) {
                 Ok(type_hash) => {
-                    if (*xudt_script_hash) == type_hash {
-                        let udt = u128::from_le_bytes(
-                            load_cell_data(index, source)?.try_into().map_err(|d| {
-                                log::error!("Parse {:?} xudt data failed: {:02x?}", source, d);
-                                SilentBerryError::CheckXUDT
-                            })?,
-                        );
-                        xudt_info.push((udt, index));
+                    if asset.is_held_by(&type_hash) {
+                        xudt_info.push(UDTCell {
+                            amount: asset.load_held_amount(index, source)?,
+                            index,
+                            lock_script_hash: load_cell_lock_hash(index, source)?.into(),
+                        });
                     }
                 }
                 Err(error) => match error {
@@ -49,25 +230,18 @@ impl UDTInfo {
         Ok(xudt_info)
     }
 
+    /// The xUDT is only moved between locks, none is minted or burned.
     pub fn check_udt(&self) -> Result<(), SilentBerryError> {
-        let mut i = 0u128;
-        for u in &self.inputs {
-            i = i.checked_add(u.0).ok_or_else(|| {
-                log::error!("CheckUDT Failed, udt overflow");
-                SilentBerryError::CheckXUDT
-            })?;
-        }
-
-        let mut o = 0u128;
-        for u in &self.inputs {
-            o = o.checked_add(u.0).ok_or_else(|| {
-                log::error!("CheckUDT Failed, udt overflow");
-                SilentBerryError::CheckXUDT
-            })?;
+        // CKB can not be minted, and the transaction fee burns some of it
+        if self.asset == Asset::CKB {
+            return Ok(());
         }
-
-        if i != o {
-            log::error!("Inputs and Outputs UDT is not equal");
+        if self.input_total != self.output_total {
+            log::error!(
+                "Inputs and Outputs UDT is not equal, inputs: {}, outputs: {}",
+                self.input_total,
+                self.output_total
+            );
             return Err(SilentBerryError::CheckXUDT);
         }
 
@@ -75,11 +249,22 @@ impl UDTInfo {
     }
 
     pub fn input_total(&self) -> u128 {
-        // Overflow is already checked in check_udt
-        let mut total = 0;
-        for (amount, _) in &self.inputs {
-            total += amount;
-        }
-        total
+        self.input_total
+    }
+
+    pub fn output_total(&self) -> u128 {
+        self.output_total
+    }
+
+    pub fn flow(&self, lock_script_hash: &Hash) -> UDTFlow {
+        self.flows
+            .iter()
+            .find(|f| f.lock_script_hash == *lock_script_hash)
+            .cloned()
+            .unwrap_or(UDTFlow {
+                lock_script_hash: lock_script_hash.clone(),
+                input: 0,
+                output: 0,
+            })
     }
 }
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use utils::{
     account_book_proof::{SMTTree, SmtKey, SmtValue, TotalAmounts, H256},
-    Hash,
+    Hash, Level,
 };
 
 #[derive(Default)]
@@ -21,16 +21,23 @@ impl AccountBook {
         self.tree.root().as_slice().try_into().unwrap()
     }
     pub fn proof(&self, k: SmtKey) -> Vec<u8> {
-        let ks: Vec<H256> = [
+        self.multi_proof(&[k])
+    }
+    pub fn multi_proof(&self, ks: &[SmtKey]) -> Vec<u8> {
+        let ks: Vec<SmtKey> = [
             SmtKey::TotalA,
             SmtKey::TotalB,
             SmtKey::TotalC,
             SmtKey::TotalD,
-            k,
         ]
         .iter()
-        .map(|k| k.get_key())
+        .chain(ks)
+        .cloned()
         .collect();
+        self.keys_proof(&ks)
+    }
+    pub fn keys_proof(&self, ks: &[SmtKey]) -> Vec<u8> {
+        let ks: Vec<H256> = ks.iter().map(|k| k.get_key()).collect();
 
         self.tree
             .merkle_proof(ks.clone())
@@ -59,15 +66,39 @@ impl AccountBook {
         smt.update(SmtKey::Auther, SmtValue::new(122));
         smt.update(SmtKey::Platform, SmtValue::new(0));
 
-        for _ in 0..100 {
-            smt.update(SmtKey::Member(new_hash(&mut c)), SmtValue::new(0));
+        for i in 0..100 {
+            smt.update(
+                SmtKey::Member(new_hash(&mut c)),
+                SmtValue::new_member(Level::B, 0, i, 0),
+            );
         }
 
-        smt.update(SmtKey::Member(new_hash(&mut 2)), SmtValue::new(21313));
-        smt.update(SmtKey::Member(new_hash(&mut 3)), SmtValue::new(4324));
-        smt.update(SmtKey::Member(new_hash(&mut 4)), SmtValue::new(4444));
-        smt.update(SmtKey::Member(new_hash(&mut 5)), SmtValue::new(555));
+        smt.update(
+            SmtKey::Member(new_hash(&mut 2)),
+            SmtValue::new_member(Level::A, 21313, 2, 0),
+        );
+        smt.update(
+            SmtKey::Member(new_hash(&mut 3)),
+            SmtValue::new_member(Level::A, 4324, 3, 0),
+        );
+        smt.update(
+            SmtKey::Member(new_hash(&mut 4)),
+            SmtValue::new_member(Level::B, 4444, 4, 0),
+        );
+        smt.update(
+            SmtKey::Member(new_hash(&mut 5)),
+            SmtValue::new_member(Level::C, 555, 5, 0),
+        );
+
+        smt
+    }
 
+    // Every buyer in the allowlist is a leaf of value 1
+    pub fn new_allowlist(buyers: &[Hash]) -> Self {
+        let mut smt: AccountBook = Default::default();
+        for buyer in buyers {
+            smt.update(SmtKey::Buyer(buyer.clone()), SmtValue::new(1));
+        }
         smt
     }
 
@@ -81,7 +112,7 @@ impl AccountBook {
     pub fn get_item(&self, k: SmtKey) -> u128 {
         let k: Hash = k.get_key().into();
         let k: [u8; 32] = k.into();
-        self.bk_items.get(&k).unwrap().clone().amount
+        self.bk_items.get(&k).unwrap().amount()
     }
 
     pub fn get_total(&self) -> TotalAmounts {
@@ -127,15 +158,44 @@ fn test_smt() {
     let root_hash_3 = smt.root_hash();
     let total_3 = smt.get_total();
 
-    let cproof = utils::account_book_proof::AccountBookProof::new(proof);
-
-    assert!(cproof
-        .verify(root_hash_1, total_1.clone(), (k.clone(), None))
-        .unwrap());
-    assert!(cproof
-        .verify(root_hash_2, total_1, (k.clone(), Some(200)))
-        .unwrap());
+    use utils::account_book_proof::{AccountBookProof, SmtLeaf};
+    let cproof = AccountBookProof::new(proof);
+
+    cproof
+        .verify(
+            root_hash_1.clone(),
+            root_hash_2.clone(),
+            &total_1
+                .leaves(&total_1)
+                .into_iter()
+                .chain([SmtLeaf::new(k.clone(), None, Some(SmtValue::new(200)))])
+                .collect::<Vec<_>>(),
+        )
+        .unwrap();
+    cproof
+        .verify(
+            root_hash_2.clone(),
+            root_hash_3,
+            &total_1
+                .leaves(&total_3)
+                .into_iter()
+                .chain([SmtLeaf::unchanged(k.clone(), Some(SmtValue::new(200)))])
+                .collect::<Vec<_>>(),
+        )
+        .unwrap();
     assert!(cproof
-        .verify(root_hash_3, total_3, (k.clone(), Some(200)))
-        .unwrap());
+        .verify(
+            root_hash_1,
+            root_hash_2,
+            &total_1
+                .leaves(&total_1)
+                .into_iter()
+                .chain([SmtLeaf::new(
+                    k,
+                    Some(SmtValue::new(0)),
+                    Some(SmtValue::new(200))
+                )])
+                .collect::<Vec<_>>(),
+        )
+        .is_err());
 }
@@ -1,7 +1,7 @@
 use account_book::AccountBook;
 use ckb_testtool::{
     ckb_types::{
-        core::{ScriptHashType, TransactionView},
+        core::{Capacity, ScriptHashType, TransactionView},
         packed::{Bytes, CellDep, CellInput, CellOutput, OutPoint, Script, WitnessArgs},
         prelude::*,
     },
@@ -14,6 +14,8 @@ use utils::{account_book_proof::TotalAmounts, Hash};
 use crate::*;
 
 pub const XUDT_OWNER_SCRIPT_HASH: [u8; 32] = [0xAA; 32];
+// Only checked when the AccountBook is created
+pub const ACCOUNT_BOOK_TYPE_ID: [u8; 32] = [0xAB; 32];
 
 pub fn get_script_hash(s: &Script) -> [u8; 32] {
     s.calc_script_hash().as_slice().try_into().unwrap()
@@ -107,6 +109,38 @@ pub fn build_xudt_cell(context: &mut Context, lock_script: Script) -> CellOutput
         .build()
 }
 
+// The all-zero xUDT script hash sells in CKB
+pub fn build_asset_script_hash(context: &mut Context, ckb: bool) -> [u8; 32] {
+    if ckb {
+        [0u8; 32]
+    } else {
+        get_opt_script_hash(&build_xudt_script(context))
+    }
+}
+
+// A cell holding `amount` of the asset, for CKB the capacity beyond the occupied capacity
+pub fn build_asset_cell(
+    context: &mut Context,
+    lock_script: Script,
+    ckb: bool,
+    amount: u128,
+) -> (CellOutput, ckb_testtool::bytes::Bytes) {
+    if ckb {
+        let cell = CellOutput::new_builder().lock(lock_script).build();
+        let occupied = cell.occupied_capacity(Capacity::zero()).unwrap().as_u64();
+        let cell = cell
+            .as_builder()
+            .capacity((occupied + amount as u64).pack())
+            .build();
+        (cell, Default::default())
+    } else {
+        (
+            build_xudt_cell(context, lock_script),
+            amount.to_le_bytes().to_vec().into(),
+        )
+    }
+}
+
 pub fn build_input_proxy_script(context: &mut Context, type_script_hash: Hash) -> Script {
     let out_point = context.deploy_cell_by_name(INPUT_TYPE_PROXY_LOCK_NAME);
     context
@@ -153,7 +187,15 @@ pub fn build_account_book_script(
     context: &mut Context,
     data: types::AccountBookData,
 ) -> Option<Script> {
-    let args = ckb_hash(
+    build_account_book_script_with_type_id(context, ACCOUNT_BOOK_TYPE_ID, data)
+}
+
+pub fn build_account_book_script_with_type_id(
+    context: &mut Context,
+    type_id: [u8; 32],
+    data: types::AccountBookData,
+) -> Option<Script> {
+    let data_hash = ckb_hash(
         data.as_builder()
             .proof(Default::default())
             .total_a(0.pack())
@@ -166,7 +208,11 @@ pub fn build_account_book_script(
     let out_point = context.deploy_cell_by_name(ACCOUNT_BOOK_NAME);
     Some(
         context
-            .build_script_with_hash_type(&out_point, ScriptHashType::Data2, args.to_vec().into())
+            .build_script_with_hash_type(
+                &out_point,
+                ScriptHashType::Data2,
+                [type_id, data_hash].concat().into(),
+            )
             .expect("build xudt"),
     )
 }
@@ -179,7 +225,7 @@ pub fn build_account_book(
     udt: (u128, u128),
 ) -> TransactionView {
     let account_book_script = build_account_book_script(context, data.clone());
-    let xudt_script = build_xudt_script(context);
+    let ckb = data.xudt_script_hash().as_slice() == [0u8; 32];
     let account_book_lock_script = build_always_suc_script(context, &[]);
     let input_proxy_script = build_input_proxy_script(
         context,
@@ -190,11 +236,9 @@ pub fn build_account_book(
             .into(),
     );
 
-    let cell_output = CellOutput::new_builder()
-        .capacity(16.pack())
-        .lock(input_proxy_script)
-        .type_(xudt_script.pack())
-        .build();
+    let (cell_input, input_data) =
+        build_asset_cell(context, input_proxy_script.clone(), ckb, udt.0);
+    let (cell_output, output_data) = build_asset_cell(context, input_proxy_script, ckb, udt.1);
     let cell_output2 = CellOutput::new_builder()
         .capacity(16.pack())
         .lock(account_book_lock_script)
@@ -202,9 +246,7 @@ pub fn build_account_book(
         .build();
 
     let cell_input = CellInput::new_builder()
-        .previous_output(
-            context.create_cell(cell_output.clone(), udt.0.to_le_bytes().to_vec().into()),
-        )
+        .previous_output(context.create_cell(cell_input, input_data))
         .build();
     let cell_input2 = CellInput::new_builder()
         .previous_output(context.create_cell(cell_output2.clone(), cell_data.0.as_bytes()))
@@ -215,7 +257,7 @@ pub fn build_account_book(
         .input(cell_input2)
         .output(cell_output)
         .output(cell_output2)
-        .output_data(udt.1.to_le_bytes().to_vec().pack())
+        .output_data(output_data.pack())
         .output_data(cell_data.1.as_bytes().pack())
         .witness(Default::default())
         .witness(
@@ -243,28 +285,51 @@ pub fn build_mint_spore(
     tx: TransactionView,
     cluster_deps: CellDep,
     spore_data: spore_types::spore::SporeData,
+) -> TransactionView {
+    let owner = build_always_suc_script(context, &[]);
+    build_mint_spores(context, tx, cluster_deps, vec![spore_data], owner)
+}
+
+pub fn build_mint_spores(
+    context: &mut Context,
+    tx: TransactionView,
+    cluster_deps: CellDep,
+    spores_data: Vec<spore_types::spore::SporeData>,
+    owner: Script,
 ) -> TransactionView {
     let (spore_out_point, spore_script_dep) =
         crate::spore::build_spore_contract_materials(context, "spore");
 
     let first_input = tx.inputs().get(0).unwrap();
-    let output_index = tx.outputs().len();
-    let type_id = crate::spore::build_type_id(&first_input, output_index);
-    let spore_type =
-        crate::spore::build_spore_type_script(context, &spore_out_point, type_id.to_vec().into());
-    let spore_output =
-        crate::spore::build_normal_output_cell_with_type(context, spore_type.clone());
+    let mut tx = tx.as_advanced_builder().cell_dep(spore_script_dep).build();
+    let mut actions = Vec::new();
+    for spore_data in spores_data {
+        let output_index = tx.outputs().len();
+        let type_id = crate::spore::build_type_id(&first_input, output_index);
+        let spore_type = crate::spore::build_spore_type_script(
+            context,
+            &spore_out_point,
+            type_id.to_vec().into(),
+        );
+        let spore_output = CellOutput::new_builder()
+            .capacity(crate::spore::UNIFORM_CAPACITY.pack())
+            .lock(owner.clone())
+            .type_(spore_type.clone().pack())
+            .build();
 
-    let tx = tx
-        .as_advanced_builder()
-        .output(spore_output)
-        .output_data(spore_data.as_slice().pack())
-        .cell_dep(spore_script_dep)
-        .build();
+        tx = tx
+            .as_advanced_builder()
+            .output(spore_output)
+            .output_data(spore_data.as_slice().pack())
+            .build();
 
-    let action =
-        crate::spore::co_build::build_mint_spore_action(context, type_id, spore_data.as_slice());
-    let actions = vec![(spore_type, action)];
+        let action = crate::spore::co_build::build_mint_spore_action_to(
+            type_id,
+            spore_data.as_slice(),
+            owner.clone(),
+        );
+        actions.push((spore_type, action));
+    }
 
     let tx = crate::spore::co_build::complete_co_build_message_with_actions(tx, &actions);
     tx.as_advanced_builder().cell_dep(cluster_deps).build()
@@ -322,30 +387,91 @@ pub fn get_account_script_hash(data: types::AccountBookData) -> [u8; 32] {
         .unwrap()
 }
 
+// `buyers` are the (owner script hash, old count, new count) of a selling with allowlist
 pub fn update_accountbook(
     context: &mut Context,
     tx: TransactionView,
     asset_amount: u128,
+    (level_offset, level_width): (u8, u8),
     total: TotalAmounts,
+    buyers: &[(Hash, u32, u32)],
 ) -> TransactionView {
     use utils::{
         account_book_proof::{SmtKey, SmtValue},
-        Hash,
+        profit::{self, ProfitConfig},
     };
 
     // Update SMT
     let mut smt = AccountBook::new_test();
     smt.update_total(total.clone());
+    for (owner_script_hash, old_count, _) in buyers {
+        if *old_count != 0 {
+            smt.update(
+                SmtKey::Buyer(owner_script_hash.clone()),
+                SmtValue::new(*old_count as u128),
+            );
+        }
+    }
     let old_smt_hash = smt.root_hash();
 
-    let sport_id: Hash = get_spore_id(&tx).into();
-
+    let mut spores = Vec::new();
+    let mut member_count = 0u32;
+    let mut config = ProfitConfig::default();
+    for (output, data) in tx.outputs().into_iter().zip(tx.outputs_data()) {
+        let spore_id: Hash = match output.type_().to_opt() {
+            Some(t) if t.code_hash().as_slice() == *SporeCodeHash => {
+                <[u8; 32]>::try_from(t.args().raw_data().to_vec())
+                    .unwrap()
+                    .into()
+            }
+            Some(t) if t.code_hash().as_slice() == *AccountBookCodeHash => {
+                let cell_data = AccountBookCellData::new_unchecked(data.raw_data());
+                member_count = cell_data.member_count().unpack();
+                config = ProfitConfig::from(&cell_data);
+                continue;
+            }
+            _ => continue,
+        };
+        let level = utils::Level::from_spore(
+            &SporeData::new_unchecked(data.raw_data()),
+            level_offset,
+            level_width,
+        )
+        .unwrap();
+        spores.push((spore_id, level));
+    }
+
+    // The input AccountBook has the shares the test set on the output, the selling raises them
+    let old_shares = config.shares;
+    let mut old_config = config.clone();
+    for (_, level) in &spores {
+        if let Some(count) = old_config.counts.get_mut(*level as usize - 1) {
+            *count -= 1;
+        }
+    }
     let mut total2 = total.clone();
-    total2.b += asset_amount;
+    let mut members = Vec::new();
+    let first_joined_at = member_count - spores.len() as u32;
+    for (joined_at, (spore_id, level)) in (first_joined_at..).zip(spores) {
+        total2.add(asset_amount, level).unwrap();
+        let share_index = profit::share_index(&old_shares, level).unwrap();
+        smt.update(
+            SmtKey::Member(spore_id.clone()),
+            SmtValue::new_member(level, 0, joined_at, share_index),
+        );
+        members.push(SmtKey::Member(spore_id));
+    }
+    let [a_share, b_share, c_share] = config.next_shares(&old_config, &total, &total2).unwrap();
     smt.update_total(total2);
-    smt.update(SmtKey::Member(sport_id.clone()), SmtValue::new(0));
+    for (owner_script_hash, _, new_count) in buyers {
+        smt.update(
+            SmtKey::Buyer(owner_script_hash.clone()),
+            SmtValue::new(*new_count as u128),
+        );
+        members.push(SmtKey::Buyer(owner_script_hash.clone()));
+    }
     let new_smt_hash = smt.root_hash();
-    let smt_proof = smt.proof(SmtKey::Member(sport_id));
+    let smt_proof = smt.multi_proof(&members);
 
     // Update tx
     let input_pos = tx
@@ -370,6 +496,9 @@ pub fn update_accountbook(
     let abcd = AccountBookCellData::new_unchecked(cell_data.clone())
         .as_builder()
         .smt_root_hash(old_smt_hash.into())
+        .a_share(old_shares[0].into())
+        .b_share(old_shares[1].into())
+        .c_share(old_shares[2].into())
         .build();
     *cell_data = abcd.as_slice().to_vec().into();
 
@@ -378,6 +507,9 @@ pub fn update_accountbook(
         AccountBookCellData::new_unchecked(outputs_data.get(input_pos).unwrap().clone().unpack())
             .as_builder()
             .smt_root_hash(new_smt_hash.into())
+            .a_share(a_share.into())
+            .b_share(b_share.into())
+            .c_share(c_share.into())
             .build();
     *outputs_data.get_mut(input_pos).unwrap() = cell_data.as_slice().to_vec().pack();
     let tx = tx
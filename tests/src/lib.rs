@@ -35,6 +35,7 @@ lazy_static::lazy_static! {
     static ref WithdrawalIntentCodeHash: [u8; 32] = get_code_hash(WITHDRAWAL_INTENT_NAME);
     static ref InputTypeProxyLockCodeHash: [u8; 32] = get_code_hash(INPUT_TYPE_PROXY_LOCK_NAME);
     static ref SporeCodeHash: [u8; 32] = get_code_hash(SPORE_NAME);
+    static ref ClusterCodeHash: [u8; 32] = get_code_hash(CLUSTER_NAME);
 }
 
 fn get_code_hash(n: &str) -> [u8; 32] {
@@ -85,9 +86,8 @@ pub fn print_tx_info(context: &Context, tx: &TransactionView) {
             let f_data = f.get_mut("data").unwrap();
             let hash = ckb_hash(&hex::decode(&f_data.as_str().unwrap()[2..]).unwrap());
 
-            let name = bins.get(&hash);
-            if name.is_some() {
-                *f_data = serde_json::to_value(format!("-- {} --", &name.unwrap())).unwrap();
+            if let Some(name) = bins.get(&hash) {
+                *f_data = serde_json::to_value(format!("-- {} --", name)).unwrap();
             }
             true
         });
@@ -169,7 +169,9 @@ pub fn verify_and_dump_failed_tx(
     max_cycles: u64,
 ) -> Result<Cycle, Error> {
     let result = context.verify_tx(tx, max_cycles);
-    if result.is_err() {
+    if let Ok(cycles) = &result {
+        println!("Cycles: {}", cycles);
+    } else {
         // let mut path = env::current_dir().expect("current dir");
         // path.push("failed_txs");
         // std::fs::create_dir_all(&path).expect("create failed_txs dir");
@@ -180,8 +182,6 @@ pub fn verify_and_dump_failed_tx(
         // std::fs::write(path, json).expect("write");
 
         print_tx_info(context, tx);
-    } else {
-        println!("Cycles: {}", result.as_ref().unwrap());
     }
     result
 }
@@ -87,6 +87,14 @@ pub fn build_mint_spore_action(
     content: &[u8],
 ) -> SporeActionUnion {
     let to = spore_internal::build_always_success_script(context, Default::default());
+    build_mint_spore_action_to(nft_id, content, to)
+}
+
+pub fn build_mint_spore_action_to(
+    nft_id: [u8; 32],
+    content: &[u8],
+    to: packed::Script,
+) -> SporeActionUnion {
     let mint = MintSpore::new_builder()
         .spore_id(h256_to_byte32(nft_id))
         .data_hash(h256_to_byte32(blake2b_256(content)))
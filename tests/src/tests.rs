@@ -1,21 +1,27 @@
 use crate::{build_tx::*, *};
 use account_book::AccountBook;
 use ckb_testtool::ckb_types::{
-    core::TransactionBuilder,
-    packed::{CellDep, CellInput, CellOutput, Script, WitnessArgs},
-    prelude::{Builder, Entity, Pack, PackVec},
+    core::{ScriptHashType, TransactionBuilder, TransactionView},
+    packed::{Bytes, CellDep, CellInput, CellOutput, Script, WitnessArgs},
+    prelude::{Builder, Entity, Pack, Unpack},
 };
 use spore_types::spore::SporeData;
 use types::{
-    AccountBookCellData, AccountBookData, BuyIntentData, DobSellingData, WithdrawalIntentData,
+    AccountBookCellData, AccountBookData, BuyIntentData, DobSellingData, MemberLeafVec,
+    WithdrawalIntentData,
 };
 use utils::{
     account_book_proof::{SmtKey, SmtValue, TotalAmounts},
-    Hash,
+    profit::{self, ProfitConfig, Share},
+    Hash, Level,
 };
 
 const DATA_ASSET_AMOUNT: u128 = 200;
-const DATA_MIN_CAPACITY: u64 = 1000;
+// The level is the 7th byte of the test DNA
+const DNA_LEVEL_OFFSET: u8 = 6;
+const DNA_LEVEL_WIDTH: u8 = 1;
+// 500 CKB, the spores of the tests occupy less
+const DATA_MIN_CAPACITY: u64 = 500_0000_0000;
 
 fn def_dob_selling_data(_context: &mut Context, spore_data: &SporeData) -> DobSellingData {
     DobSellingData::new_builder()
@@ -40,6 +46,8 @@ fn def_withdrawal_intent_data(context: &mut Context) -> WithdrawalIntentData {
     WithdrawalIntentData::new_builder()
         .xudt_script_hash(get_opt_script_hash(&build_xudt_script(context)).pack())
         .spore_id([0u8; 32].pack())
+        .spore_code_hash((*SporeCodeHash).pack())
+        .spore_hash_type((ScriptHashType::Data1 as u8).into())
         .cluster_id([0u8; 32].pack())
         .expire_since(1000u64.pack())
         .owner_script_hash([0u8; 32].pack())
@@ -52,7 +60,12 @@ fn def_account_book_data(context: &mut Context) -> AccountBookData {
         .withdrawal_intent_code_hash((*WithdrawalIntentCodeHash).pack())
         .xudt_script_hash(get_opt_script_hash(&build_xudt_script(context)).pack())
         .input_type_proxy_lock_code_hash((*InputTypeProxyLockCodeHash).pack())
+        .spore_code_hash((*SporeCodeHash).pack())
+        .spore_hash_type((ScriptHashType::Data1 as u8).into())
+        .cluster_code_hash((*ClusterCodeHash).pack())
         .cluster_id([3u8; 32].pack())
+        .level_offset(DNA_LEVEL_OFFSET.into())
+        .level_width(DNA_LEVEL_WIDTH.into())
         .build()
 }
 fn def_account_book_cell_data(_context: &mut Context) -> AccountBookCellData {
@@ -95,7 +108,7 @@ fn def_account_book_cell_data(_context: &mut Context) -> AccountBookCellData {
 fn def_spore(context: &mut Context) -> (SporeData, CellDep) {
     let (cluster_id, cluster_deps) = build_cluster(context, ("Spore Cluster", "Test Cluster"));
     let spore_data = crate::spore::build_serialized_spore_data(
-        "{\"dna\":\"4000000000002\"}".as_bytes().to_vec(),
+        "{\"dna\":\"40000000000002\"}".as_bytes().to_vec(),
         "dob/1",
         Some(cluster_id.to_vec()),
     );
@@ -112,93 +125,217 @@ fn get_cluster_id(d: &SporeData) -> [u8; 32] {
         .unwrap()
 }
 
-#[test]
-fn test_simple_buy_intent() {
-    let mut context = new_context();
+// Buyer pays 1000 xUDT in, gets `change` xUDT back in the output at `change_location`.
+fn build_buy_intent_tx(
+    context: &mut Context,
+    capacity: u64,
+    change_location: u32,
+    change: u128,
+) -> TransactionView {
+    build_buy_intent_tx_in(context, false, capacity, change_location, change)
+}
 
-    let lock_script = build_user1_script(&mut context);
-    let udt_cell = build_xudt_cell(&mut context, lock_script.clone());
+// Paid in xUDT, or in CKB if `ckb`
+fn build_buy_intent_tx_in(
+    context: &mut Context,
+    ckb: bool,
+    capacity: u64,
+    change_location: u32,
+    change: u128,
+) -> TransactionView {
+    let lock_script = build_user1_script(context);
+    let (udt_cell, udt_data) = build_asset_cell(context, lock_script.clone(), ckb, 1000);
 
-    let inputs = vec![
-        build_input(context.create_cell(udt_cell.clone(), 1000u128.to_le_bytes().to_vec().into())),
-        build_input(build_out_point1(&mut context, lock_script.clone())),
-    ];
+    let mut inputs = vec![build_input(context.create_cell(udt_cell, udt_data))];
+    if !ckb {
+        // Pays the capacity of the outputs, in CKB the funding cell does
+        inputs.push(build_input(build_out_point1(context, lock_script.clone())));
+    }
 
-    let (spore_data, _) = def_spore(&mut context);
-    let dob_selling_data = def_dob_selling_data(&mut context, &spore_data);
-    let dob_selling = build_dob_selling_script(&mut context, &dob_selling_data);
-    let dob_selling_udt = build_xudt_cell(&mut context, dob_selling.clone());
+    let (spore_data, _) = def_spore(context);
+    let dob_selling_data = def_dob_selling_data(context, &spore_data);
+    let dob_selling = build_dob_selling_script(context, &dob_selling_data);
+    let (dob_selling_udt, dob_selling_udt_data) =
+        build_asset_cell(context, dob_selling, ckb, DATA_ASSET_AMOUNT);
+    let (change_cell, change_data) = build_asset_cell(context, lock_script.clone(), ckb, change);
 
-    let buy_intent_data = def_buy_intent_data(&mut context, &dob_selling_data);
+    let buy_intent_data = def_buy_intent_data(context, &dob_selling_data)
+        .as_builder()
+        .xudt_script_hash(build_asset_script_hash(context, ckb).pack())
+        .change_script_hash(lock_script.calc_script_hash())
+        .change_location(change_location.pack())
+        .build();
 
     let buy_intent_script = build_buy_intent_cell(
-        &mut context,
-        1000,
+        context,
+        capacity,
         lock_script,
         &[[0u8; 32], ckb_hash(buy_intent_data.as_slice())].concat(),
     );
 
-    let outputs = vec![
-        udt_cell.clone(),
-        dob_selling_udt.clone(),
-        buy_intent_script.clone(),
-    ];
-
-    let outputs_data: Vec<ckb_testtool::ckb_types::packed::Bytes> = vec![
-        800u128.to_le_bytes().to_vec().pack(),
-        DATA_ASSET_AMOUNT.to_le_bytes().to_vec().pack(),
-        Default::default(),
+    let mut outputs = vec![
+        (
+            dob_selling_udt,
+            dob_selling_udt_data.pack(),
+            Default::default(),
+        ),
+        (
+            buy_intent_script.clone(),
+            Default::default(),
+            WitnessArgs::new_builder()
+                .output_type(Some(buy_intent_data.as_bytes()).pack())
+                .build()
+                .as_slice()
+                .pack(),
+        ),
     ];
+    outputs.insert(
+        change_location as usize,
+        (change_cell, change_data.pack(), Default::default()),
+    );
 
-    let witnesses = vec![
-        Default::default(),
-        Default::default(),
-        WitnessArgs::new_builder()
-            .output_type(Some(buy_intent_data.as_bytes()).pack())
-            .build()
-            .as_slice()
-            .pack(),
-    ];
+    let mut tx = TransactionBuilder::default().inputs(inputs);
+    for (output, data, witness) in outputs {
+        tx = tx.output(output).output_data(data).witness(witness);
+    }
+    context.complete_tx(tx.build())
+}
 
-    let tx = context.complete_tx(
-        TransactionBuilder::default()
-            .inputs(inputs)
-            .outputs(outputs)
-            .outputs_data(outputs_data.pack())
-            .witnesses(witnesses)
-            .build(),
-    );
+#[test]
+fn test_simple_buy_intent() {
+    let mut context = new_context();
+    let tx = build_buy_intent_tx(&mut context, DATA_MIN_CAPACITY, 0, 800);
     // print_tx_info(&context, &tx);
     verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect("pass");
 }
 
 #[test]
-fn test_revocation_buy_intent() {
+fn test_buy_intent_ckb() {
+    let mut context = new_context();
+    let tx = build_buy_intent_tx_in(&mut context, true, DATA_MIN_CAPACITY, 0, 700);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect("pass");
+}
+
+#[test]
+fn test_buy_intent_ckb_underpaid() {
+    let mut context = new_context();
+    let tx = build_buy_intent_tx_in(&mut context, true, DATA_MIN_CAPACITY, 0, 700);
+    // The DobSelling gets one shannon less than the asset amount
+    let mut outputs: Vec<CellOutput> = tx.outputs().into_iter().collect();
+    let capacity: u64 = outputs[1].capacity().unpack();
+    outputs[1] = outputs[1]
+        .clone()
+        .as_builder()
+        .capacity((capacity - 1).pack())
+        .build();
+    let tx = tx.as_advanced_builder().set_outputs(outputs).build();
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("underpaid");
+}
+
+#[test]
+fn test_buy_intent_change_location() {
+    let mut context = new_context();
+    let tx = build_buy_intent_tx(&mut context, DATA_MIN_CAPACITY, 2, 800);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect("pass");
+}
+
+#[test]
+fn test_buy_intent_change_missing() {
+    let mut context = new_context();
+    let tx = build_buy_intent_tx(&mut context, DATA_MIN_CAPACITY, 0, 700);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("xUDT change 700 != 800");
+}
+
+// Appends `extension` to the data of the xUDT outputs
+fn with_xudt_extension(
+    context: &mut Context,
+    tx: TransactionView,
+    extension: &[u8],
+) -> TransactionView {
+    let xudt_script = build_xudt_script(context);
+    let outputs_data: Vec<Bytes> = tx
+        .outputs()
+        .into_iter()
+        .zip(tx.outputs_data())
+        .map(|(output, data)| {
+            if output.type_().to_opt() == xudt_script {
+                [data.raw_data().as_ref(), extension].concat().pack()
+            } else {
+                data
+            }
+        })
+        .collect();
+    tx.as_advanced_builder()
+        .set_outputs_data(outputs_data)
+        .build()
+}
+
+#[test]
+fn test_buy_intent_xudt_extension() {
+    let mut context = new_context();
+    let tx = build_buy_intent_tx(&mut context, DATA_MIN_CAPACITY, 0, 800);
+    let tx = with_xudt_extension(&mut context, tx, &[0xEE; 20]);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect("pass");
+}
+
+#[test]
+fn test_buy_intent_change_elsewhere() {
+    let mut context = new_context();
+    let tx = build_buy_intent_tx(&mut context, DATA_MIN_CAPACITY, 0, 700);
+    // The other 100 xUDT goes to another lock
+    let other_lock_script = build_user2_script(&mut context);
+    let tx = tx
+        .as_advanced_builder()
+        .output(build_xudt_cell(&mut context, other_lock_script))
+        .output_data(100u128.to_le_bytes().to_vec().pack())
+        .build();
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("xUDT change elsewhere");
+}
+
+#[test]
+fn test_buy_intent_min_capacity() {
     let mut context = new_context();
-    let def_lock_script: Script = build_always_suc_script(&mut context, &[]);
-    let (spore_data, _cluster_deps) = def_spore(&mut context);
+    let tx = build_buy_intent_tx(&mut context, DATA_MIN_CAPACITY - 1, 0, 800);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("capacity < min_capacity");
+}
+
+// Revokes a BuyIntent, which expires at block 1000, with the input `since`.
+fn build_revocation_buy_intent_tx(context: &mut Context, since: u64) -> TransactionView {
+    build_revocation_buy_intent_tx_in(context, false, since)
+}
+
+// The buyer does not sign the revocation, the asset of its DobSelling and the capacity of
+// both go back to it.
+fn build_revocation_buy_intent_tx_in(
+    context: &mut Context,
+    ckb: bool,
+    since: u64,
+) -> TransactionView {
+    let def_lock_script: Script = build_always_suc_script(context, &[]);
+    let buyer_lock_script: Script = build_always_suc_script(context, &[0x22; 32]);
+    let (spore_data, _cluster_deps) = def_spore(context);
     let account_book_script_hash = [0u8; 32];
+    let intent_capacity = 200_0000_0000u64;
 
     // DOB Selling
-    let dob_selling_data = def_dob_selling_data(&mut context, &spore_data)
+    let dob_selling_data = def_dob_selling_data(context, &spore_data)
         .as_builder()
         .owner_script_hash(def_lock_script.calc_script_hash())
         .build();
     let cell_input_dob_selling = {
-        let dob_selling = build_dob_selling_script(&mut context, &dob_selling_data);
-        let dob_selling_udt = build_xudt_cell(&mut context, dob_selling.clone());
+        let dob_selling = build_dob_selling_script(context, &dob_selling_data);
+        let (cell, data) = build_asset_cell(context, dob_selling, ckb, DATA_ASSET_AMOUNT);
 
         CellInput::new_builder()
-            .previous_output(context.create_cell(
-                dob_selling_udt.clone(),
-                DATA_ASSET_AMOUNT.to_le_bytes().to_vec().into(),
-            ))
+            .previous_output(context.create_cell(cell, data))
             .build()
     };
+    let (refund, refund_data) =
+        build_asset_cell(context, buyer_lock_script.clone(), ckb, DATA_ASSET_AMOUNT);
     let tx = TransactionBuilder::default()
         .input(cell_input_dob_selling)
-        .output(build_xudt_cell(&mut context, def_lock_script.clone()))
-        .output_data(DATA_ASSET_AMOUNT.to_le_bytes().to_vec().pack())
+        .output(refund)
+        .output_data(refund_data.pack())
         .witness(
             WitnessArgs::new_builder()
                 .lock(Some(dob_selling_data.as_bytes()).pack())
@@ -209,14 +346,15 @@ fn test_revocation_buy_intent() {
         .build();
 
     // Buy Intent
-    let buy_intent_data = def_buy_intent_data(&mut context, &dob_selling_data)
+    let buy_intent_data = def_buy_intent_data(context, &dob_selling_data)
         .as_builder()
-        .owner_script_hash(def_lock_script.calc_script_hash())
+        .xudt_script_hash(build_asset_script_hash(context, ckb).pack())
+        .owner_script_hash(buyer_lock_script.calc_script_hash())
         .build();
     let cell_input_buy_intent = {
         let buy_intent_script = build_buy_intent_cell(
-            &mut context,
-            1000,
+            context,
+            intent_capacity,
             def_lock_script.clone(),
             &[
                 account_book_script_hash,
@@ -227,7 +365,7 @@ fn test_revocation_buy_intent() {
 
         CellInput::new_builder()
             .previous_output(context.create_cell(buy_intent_script.clone(), Default::default()))
-            .since(10000.pack())
+            .since(since.pack())
             .build()
     };
 
@@ -236,8 +374,8 @@ fn test_revocation_buy_intent() {
         .input(cell_input_buy_intent)
         .output(
             CellOutput::new_builder()
-                .capacity(1000u64.pack())
-                .lock(def_lock_script.clone())
+                .capacity(intent_capacity.pack())
+                .lock(buyer_lock_script)
                 .build(),
         )
         .output_data(Default::default())
@@ -250,212 +388,1464 @@ fn test_revocation_buy_intent() {
         )
         .build();
 
-    let tx = context.complete_tx(tx);
+    context.complete_tx(tx)
+}
+
+#[test]
+fn test_revocation_buy_intent() {
+    let mut context = new_context();
+    let tx = build_revocation_buy_intent_tx(&mut context, 10000);
     // print_tx_info(&context, &tx);
     verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect("pass");
 }
 
 #[test]
-fn test_simple_selling() {
+fn test_revocation_buy_intent_ckb() {
     let mut context = new_context();
-    let def_lock_script: Script = build_always_suc_script(&mut context, &[]);
-    let (spore_data, cluster_deps) = def_spore(&mut context);
+    let tx = build_revocation_buy_intent_tx_in(&mut context, true, 10000);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect("pass");
+}
 
-    let tx = TransactionBuilder::default().build();
+#[test]
+fn test_revocation_buy_intent_ckb_skim() {
+    let mut context = new_context();
+    let tx = build_revocation_buy_intent_tx_in(&mut context, true, 10000);
 
-    // Account Book
-    let account_book_data = def_account_book_data(&mut context);
-    let account_book_data = account_book_data
+    // The buyer does not sign, the submitter can not keep any of the refund as the fee
+    let mut outputs: Vec<CellOutput> = tx.outputs().into_iter().collect();
+    let capacity: u64 = outputs[0].capacity().unpack();
+    outputs[0] = outputs[0]
+        .clone()
         .as_builder()
-        .cluster_id(get_cluster_id(&spore_data).pack())
-        // .proof(smt_proof.pack())
+        .capacity((capacity - 1).pack())
         .build();
-    let ab_cell_data = def_account_book_cell_data(&mut context)
+    let tx = tx.as_advanced_builder().set_outputs(outputs).build();
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("refund skimmed");
+}
+
+#[test]
+fn test_revocation_buy_intent_refund_elsewhere() {
+    let mut context = new_context();
+    let tx = build_revocation_buy_intent_tx(&mut context, 10000);
+
+    // The xUDT of the DobSelling does not go back to the buyer
+    let other_lock_script = build_always_suc_script(&mut context, &[0x99; 32]);
+    let mut outputs: Vec<CellOutput> = tx.outputs().into_iter().collect();
+    outputs[0] = outputs[0]
+        .clone()
         .as_builder()
-        // .smt_root_hash(old_smt_hash.into())
-        .member_count(35u32.pack())
+        .lock(other_lock_script)
         .build();
-    let ab_cell_data_new = ab_cell_data
+    let tx = tx.as_advanced_builder().set_outputs(outputs).build();
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("refund elsewhere");
+}
+
+#[test]
+fn test_revocation_buy_intent_capacity_elsewhere() {
+    let mut context = new_context();
+    let tx = build_revocation_buy_intent_tx(&mut context, 10000);
+
+    // The owner gets an output, but not the capacity of the intent
+    let mut outputs: Vec<CellOutput> = tx.outputs().into_iter().collect();
+    let capacity: u64 = outputs[1].capacity().unpack();
+    outputs[1] = outputs[1]
         .clone()
         .as_builder()
-        // .smt_root_hash(new_smt_hash.into())
-        .member_count(36u32.pack())
+        .capacity((capacity - 1).pack())
         .build();
+    let tx = tx.as_advanced_builder().set_outputs(outputs).build();
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("capacity elsewhere");
+}
 
-    let tx = build_account_book(
-        &mut context,
-        tx,
-        account_book_data.clone(),
-        (ab_cell_data, ab_cell_data_new),
-        (10000, 10000 + DATA_ASSET_AMOUNT),
-    );
-    let account_book_script_hash = get_account_script_hash(account_book_data);
+#[test]
+fn test_revocation_buy_intent_without_dob_selling() {
+    let mut context = new_context();
+    let tx = build_revocation_buy_intent_tx(&mut context, 10000);
 
-    // DOB Selling
-    let dob_selling_data = def_dob_selling_data(&mut context, &spore_data)
-        .as_builder()
-        .account_book_script_hash(account_book_script_hash.pack())
+    // The BuyIntent alone, the DobSelling would be left without it
+    let inputs: Vec<CellInput> = tx.inputs().into_iter().skip(1).collect();
+    let witnesses: Vec<Bytes> = tx.witnesses().into_iter().skip(1).collect();
+    let outputs: Vec<CellOutput> = tx.outputs().into_iter().skip(1).collect();
+    let outputs_data: Vec<Bytes> = tx.outputs_data().into_iter().skip(1).collect();
+    let tx = tx
+        .as_advanced_builder()
+        .set_inputs(inputs)
+        .set_witnesses(witnesses)
+        .set_outputs(outputs)
+        .set_outputs_data(outputs_data)
         .build();
-    let cell_input_dob_selling = {
-        let dob_selling = build_dob_selling_script(&mut context, &dob_selling_data);
-        let dob_selling_udt = build_xudt_cell(&mut context, dob_selling.clone());
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("DobSelling left");
+}
 
-        CellInput::new_builder()
-            .previous_output(context.create_cell(
-                dob_selling_udt.clone(),
-                DATA_ASSET_AMOUNT.to_le_bytes().to_vec().into(),
-            ))
-            .build()
-    };
+#[test]
+fn test_revocation_dob_selling_not_returned() {
+    let mut context = new_context();
+    let tx = build_revocation_buy_intent_tx(&mut context, 10000);
+
+    // The submitter keeps 1 xUDT of the payment
+    let other_lock_script = build_always_suc_script(&mut context, &[0x99; 32]);
+    let mut outputs_data: Vec<Bytes> = tx.outputs_data().into_iter().collect();
+    outputs_data[0] = (DATA_ASSET_AMOUNT - 1).to_le_bytes().to_vec().pack();
     let tx = tx
         .as_advanced_builder()
-        .input(cell_input_dob_selling)
-        .output(
-            CellOutput::new_builder()
-                .lock(def_lock_script.clone())
-                .capacity(1000.pack())
-                .build(),
-        )
-        .output_data(Default::default())
-        .witness(
-            WitnessArgs::new_builder()
-                .lock(Some(dob_selling_data.as_bytes()).pack())
-                .build()
-                .as_bytes()
-                .pack(),
-        )
+        .set_outputs_data(outputs_data)
+        .output(build_xudt_cell(&mut context, other_lock_script))
+        .output_data(1u128.to_le_bytes().to_vec().pack())
         .build();
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("xUDT not returned");
+}
 
-    // Buy Intent
-    let buy_intent_data = def_buy_intent_data(&mut context, &dob_selling_data);
-    let cell_input_buy_intent = {
-        let buy_intent_script = build_buy_intent_cell(
-            &mut context,
-            1000,
-            def_lock_script.clone(),
-            &[
-                account_book_script_hash,
-                ckb_hash(buy_intent_data.as_slice()),
-            ]
-            .concat(),
-        );
+#[test]
+fn test_revocation_buy_intent_not_expired() {
+    let mut context = new_context();
+    let tx = build_revocation_buy_intent_tx(&mut context, 999);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("block 999 < 1000");
+}
 
-        CellInput::new_builder()
-            .previous_output(context.create_cell(buy_intent_script.clone(), Default::default()))
-            .build()
+#[test]
+fn test_revocation_buy_intent_since_metric() {
+    let mut context = new_context();
+    // Epoch 10000, the expiry is a block number
+    let since = (0b0010_0000u64 << 56) | 10000;
+    let tx = build_revocation_buy_intent_tx(&mut context, since);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("epoch vs block number");
+}
+
+#[test]
+fn test_since() {
+    use utils::Since;
+    let epoch = |number: u64, index: u64, length: u64| {
+        Since::try_from((0b0010_0000u64 << 56) | (length << 40) | (index << 24) | number)
     };
+    let reaches = |a: Since, b: Since| a.reaches(&b).ok();
 
-    let tx = tx
-        .as_advanced_builder()
-        .input(cell_input_buy_intent)
+    // 1 + 1/2 epochs >= 1 + 2/4 epochs > 1 + 1/3 epochs
+    assert_eq!(
+        reaches(epoch(1, 1, 2).unwrap(), epoch(1, 2, 4).unwrap()),
+        Some(true)
+    );
+    assert_eq!(
+        reaches(epoch(1, 1, 3).unwrap(), epoch(1, 2, 4).unwrap()),
+        Some(false)
+    );
+    assert_eq!(
+        reaches(epoch(2, 0, 0).unwrap(), epoch(1, 99, 100).unwrap()),
+        Some(true)
+    );
+    assert!(epoch(1, 2, 2).is_err());
+    assert!(epoch(1, 1, 0).is_err());
+
+    let block = Since::try_from(1000).unwrap();
+    let relative_block = Since::try_from((0b1000_0000u64 << 56) | 1000).unwrap();
+    let timestamp = Since::try_from((0b0100_0000u64 << 56) | 1000).unwrap();
+    assert_eq!(reaches(Since::try_from(1001).unwrap(), block), Some(true));
+    assert_eq!(reaches(Since::try_from(999).unwrap(), block), Some(false));
+    assert_eq!(reaches(relative_block, block), None);
+    assert_eq!(reaches(timestamp, block), None);
+    assert_eq!(reaches(epoch(1000, 0, 1).unwrap(), block), None);
+
+    // Metric 0b11 and the reserved bits are invalid
+    assert!(Since::try_from(0b0110_0000u64 << 56).is_err());
+    assert!(Since::try_from(1u64 << 56).is_err());
+
+    // The timestamp in milliseconds overflows
+    let timestamp = |seconds: u64| Since::try_from((0b0100_0000u64 << 56) | seconds);
+    assert!(timestamp(u64::MAX / 1000).is_ok());
+    assert!(timestamp(u64::MAX / 1000 + 1).is_err());
+    assert!(timestamp(0x00ff_ffff_ffff_ffff).is_err());
+}
+
+// A DobSelling without BuyIntent is revoked in a transaction signed by the `signer` lock, the
+// xUDT goes to the owner.
+fn build_revocation_dob_selling_tx(context: &mut Context, signer: &[u8]) -> TransactionView {
+    let seller_lock_script: Script = build_always_suc_script(context, &[0x66; 32]);
+    let signer_lock_script: Script = build_always_suc_script(context, signer);
+    let (spore_data, _cluster_deps) = def_spore(context);
+
+    let dob_selling_data = def_dob_selling_data(context, &spore_data)
+        .as_builder()
+        .owner_script_hash(seller_lock_script.calc_script_hash())
+        .build();
+    let dob_selling = build_dob_selling_script(context, &dob_selling_data);
+    let dob_selling_udt = build_xudt_cell(context, dob_selling);
+    let cell_input_dob_selling = build_input(context.create_cell(
+        dob_selling_udt,
+        DATA_ASSET_AMOUNT.to_le_bytes().to_vec().into(),
+    ));
+
+    let tx = TransactionBuilder::default()
+        .input(cell_input_dob_selling)
+        .input(build_input(build_out_point1(context, signer_lock_script)))
+        .output(build_xudt_cell(context, seller_lock_script))
+        .output_data(DATA_ASSET_AMOUNT.to_le_bytes().to_vec().pack())
         .witness(
             WitnessArgs::new_builder()
-                .input_type(Some(buy_intent_data.as_bytes()).pack())
+                .lock(Some(dob_selling_data.as_bytes()).pack())
                 .build()
                 .as_bytes()
                 .pack(),
         )
         .build();
-
-    // Spore
-    let tx = build_mint_spore(&mut context, tx, cluster_deps, spore_data);
-
-    let tx = update_accountbook(
-        &mut context,
-        tx,
-        DATA_ASSET_AMOUNT,
-        TotalAmounts {
-            a: 5000,
-            b: 5000,
-            c: 0,
-            d: 0,
-        },
-    );
-    let tx = context.complete_tx(tx);
-    // print_tx_info(&context, &tx);
-    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect("pass");
+    context.complete_tx(tx)
 }
 
 #[test]
-fn test_simple_withdrawal_intent() {
-    // Add Spore
+fn test_revocation_dob_selling_without_buy_intent() {
     let mut context = new_context();
-    let tx = TransactionBuilder::default().build();
-    let def_lock_script = build_always_suc_script(&mut context, &[]);
+    // The payment of the buyer can not be taken by the owner, a third party, or the buyer
+    // without the expired BuyIntent
+    for signer in [[0x66; 32], [0x99; 32], [0x77; 32]] {
+        let tx = build_revocation_dob_selling_tx(&mut context, &signer);
+        verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("no BuyIntent");
+    }
+}
 
-    let (spore_data, _cluster_dep) = def_spore(&mut context);
-    let tx = build_transfer_spore(&mut context, tx, &spore_data);
-    let tx = context.complete_tx(tx);
+fn build_account_book_creation(context: &mut Context, cluster_lock_args: &[u8]) -> TransactionView {
+    build_account_book_creation_with_type_id(context, cluster_lock_args, None)
+}
+
+// The Type ID is calculated from the first input if `None`
+fn build_account_book_creation_with_type_id(
+    context: &mut Context,
+    cluster_lock_args: &[u8],
+    type_id: Option<[u8; 32]>,
+) -> TransactionView {
+    let def_lock_script: Script = build_always_suc_script(context, &[]);
+
+    // Cluster
+    let (cluster_out_point, cluster_script_dep) =
+        crate::spore::build_spore_contract_materials(context, CLUSTER_NAME);
+    let cluster_data = crate::spore::build_serialized_cluster_data("Spore Cluster", "Test Cluster");
+    let (cluster_id, cluster_type, _, cluster_output, _) = crate::spore::build_cluster_materials(
+        context,
+        &cluster_out_point,
+        cluster_data.clone(),
+        0,
+        &[],
+    );
+    let cluster_lock_script = build_always_suc_script(context, cluster_lock_args);
+    let cluster_input = build_input(
+        context.create_cell(
+            CellOutput::new_builder()
+                .capacity(1000.pack())
+                .lock(cluster_lock_script)
+                .type_(cluster_type.clone().pack())
+                .build(),
+            cluster_data.as_bytes(),
+        ),
+    );
 
-    let withdrawal_intent_data = def_withdrawal_intent_data(&mut context)
+    // Account Book
+    let account_book_data = def_account_book_data(context)
         .as_builder()
-        .spore_id(get_spore_id(&tx).pack())
-        .spore_level(2.into())
-        .cluster_id(get_cluster_id(&spore_data).pack())
+        .cluster_id(cluster_id.pack())
         .build();
-    let withdrawal_intent_script =
-        build_withdrawal_intent_script(&mut context, &withdrawal_intent_data, [0u8; 32].into());
-    // Inputs: CKB + Spore
-    // Output: Withdrawal intent + Spore
-    let tx = tx
-        .as_advanced_builder()
-        .input(build_input(build_out_point1(
-            &mut context,
-            def_lock_script.clone(),
-        )))
+    let account_book_cell_data = def_account_book_cell_data(context)
+        .as_builder()
+        .smt_root_hash(utils::account_book_proof::genesis_root().unwrap().into())
+        .owner_script_hash(def_lock_script.calc_script_hash())
+        .build();
+    // The AccountBook is Outputs[1]
+    let type_id = type_id
+        .unwrap_or_else(|| ckb_hash(&[cluster_input.as_slice(), &1u64.to_le_bytes()].concat()));
+    let account_book_script =
+        build_account_book_script_with_type_id(context, type_id, account_book_data.clone());
+
+    let tx = TransactionBuilder::default()
+        .input(cluster_input)
+        .output(cluster_output)
+        .output_data(cluster_data.as_slice().pack())
+        .witness(Default::default())
         .output(
             CellOutput::new_builder()
-                .lock(def_lock_script)
-                .type_(withdrawal_intent_script.pack())
                 .capacity(1000.pack())
+                .lock(def_lock_script)
+                .type_(account_book_script.pack())
                 .build(),
         )
-        .output_data(Default::default())
+        .output_data(account_book_cell_data.as_slice().pack())
         .witness(
             WitnessArgs::new_builder()
-                .output_type(Some(withdrawal_intent_data.as_bytes()).pack())
+                .output_type(Some(account_book_data.as_bytes()).pack())
                 .build()
-                .as_slice()
+                .as_bytes()
                 .pack(),
         )
+        .cell_dep(cluster_script_dep)
         .build();
 
-    let tx = context.complete_tx(tx);
-    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect("pass");
+    let action = crate::spore::co_build::build_transfer_cluster_action(context, cluster_id);
+    let tx = crate::spore::co_build::complete_co_build_message_with_actions(
+        tx,
+        &[(cluster_type, action)],
+    );
+    context.complete_tx(tx)
 }
 
 #[test]
-fn test_simple_withdrawal_suc() {
+fn test_account_book_creation() {
     let mut context = new_context();
-    let tx = TransactionBuilder::default().build();
-    let def_lock_script = build_always_suc_script(&mut context, &[0x11; 32]);
-    let xudt_script = build_xudt_script(&mut context);
-
-    let spore_id: Hash = [0x1B; 32].into();
-    // let spore_level: u8 = 1;
-    let cluster_id: Hash = [0x1A; 32].into();
-    let old_amount = 10u128;
+    let tx = build_account_book_creation(&mut context, &[]);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect("pass");
+}
 
-    let mut smt = AccountBook::new_test();
-    smt.update(SmtKey::Member(spore_id.clone()), SmtValue::new(old_amount));
+#[test]
+fn test_account_book_creation_without_cluster_owner() {
+    let mut context = new_context();
+    let tx = build_account_book_creation(&mut context, &[0x12; 32]);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("cluster is not owned");
+}
 
-    let old_hash = smt.root_hash();
-    // let totals = smt.get_total();
+#[test]
+fn test_account_book_creation_without_quota() {
+    let mut context = new_context();
+    let tx = build_account_book_creation(&mut context, &[]);
 
-    // Account Book
-    let account_book_cell_data = def_account_book_cell_data(&mut context)
-        .as_builder()
-        .smt_root_hash(old_hash.into())
-        .build();
-    let account_book_data = def_account_book_data(&mut context)
+    // Level A has no quota, but the other levels give it a member column
+    let mut outputs_data: Vec<Bytes> = tx.outputs_data().into_iter().collect();
+    outputs_data[1] = AccountBookCellData::new_unchecked(outputs_data[1].raw_data())
         .as_builder()
-        .cluster_id(cluster_id.clone().into())
+        .a_num(0u32.pack())
+        .build()
+        .as_slice()
+        .pack();
+    let tx = tx
+        .as_advanced_builder()
+        .set_outputs_data(outputs_data)
+        .build();
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("member column without quota");
+}
+
+#[test]
+fn test_account_book_creation_type_id() {
+    let mut context = new_context();
+    // The args of an existing AccountBook can not be reused
+    let tx =
+        build_account_book_creation_with_type_id(&mut context, &[], Some(ACCOUNT_BOOK_TYPE_ID));
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("type id");
+}
+
+// The owner updates the AccountBook cell data with `update`
+fn build_update_tx(
+    context: &mut Context,
+    owner_lock_args: &[u8],
+    update: impl FnOnce(types::AccountBookCellDataBuilder) -> types::AccountBookCellDataBuilder,
+) -> TransactionView {
+    let def_lock_script = build_always_suc_script(context, &[]);
+    let owner_lock_script = build_always_suc_script(context, &[0x55; 32]);
+    let signer_lock_script = build_always_suc_script(context, owner_lock_args);
+
+    let account_book_data = def_account_book_data(context);
+    let account_book_script = build_account_book_script(context, account_book_data.clone());
+    let cell_data = def_account_book_cell_data(context)
+        .as_builder()
+        .owner_script_hash(owner_lock_script.calc_script_hash())
+        .build();
+    let new_cell_data = update(cell_data.clone().as_builder()).build();
+
+    let account_book_cell = CellOutput::new_builder()
+        .capacity(1000.pack())
+        .lock(def_lock_script)
+        .type_(account_book_script.pack())
+        .build();
+    let tx = TransactionBuilder::default()
+        .input(build_input(
+            context.create_cell(account_book_cell.clone(), cell_data.as_bytes()),
+        ))
+        .input(build_input(build_out_point1(context, signer_lock_script)))
+        .output(account_book_cell)
+        .output_data(new_cell_data.as_slice().pack())
+        .witness(
+            WitnessArgs::new_builder()
+                .output_type(Some(account_book_data.as_bytes()).pack())
+                .build()
+                .as_bytes()
+                .pack(),
+        )
+        .build();
+    context.complete_tx(tx)
+}
+
+#[test]
+fn test_update_account_book() {
+    let mut context = new_context();
+    let tx = build_update_tx(&mut context, &[0x55; 32], |data| {
+        data.asset_amount(300u128.pack())
+            .platform_id([9u8; 32].pack())
+            .a_profit(
+                types::AProfit::new_builder()
+                    .set([30u8.into(), 70u8.into()])
+                    .build(),
+            )
+    });
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect("pass");
+}
+
+#[test]
+fn test_update_account_book_pause() {
+    let mut context = new_context();
+    let tx = build_update_tx(&mut context, &[0x55; 32], |data| data.paused(1.into()));
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect("pass");
+
+    let tx = build_update_tx(&mut context, &[0x55; 32], |data| data.paused(2.into()));
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("invalid paused flag");
+}
+
+fn account_book_input_pos(context: &Context, tx: &TransactionView) -> usize {
+    tx.inputs()
+        .into_iter()
+        .position(|input| {
+            let (output, _) = context.get_cell(&input.previous_output()).unwrap();
+            output
+                .type_()
+                .to_opt()
+                .is_some_and(|t| t.code_hash().as_slice() == *AccountBookCodeHash)
+        })
+        .unwrap()
+}
+
+// Apply `update` to both the input and output AccountBook cell data in the transaction
+fn update_account_book_cell_data(
+    context: &mut Context,
+    tx: TransactionView,
+    update: impl Fn(types::AccountBookCellDataBuilder) -> types::AccountBookCellDataBuilder,
+) -> TransactionView {
+    let pos = account_book_input_pos(context, &tx);
+    let update = |data: &[u8]| {
+        update(AccountBookCellData::new_unchecked(data.to_vec().into()).as_builder()).build()
+    };
+
+    let out_point = tx.inputs().get(pos).unwrap().previous_output();
+    let (_, cell_data) = context.cells.get_mut(&out_point).unwrap();
+    *cell_data = update(cell_data).as_bytes();
+
+    let mut outputs_data: Vec<Bytes> = tx.outputs_data().into_iter().collect();
+    outputs_data[pos] = update(&outputs_data[pos].raw_data()).as_slice().pack();
+    tx.as_advanced_builder()
+        .set_outputs_data(outputs_data)
+        .build()
+}
+
+fn pause_account_book(context: &mut Context, tx: TransactionView) -> TransactionView {
+    update_account_book_cell_data(context, tx, |data| data.paused(1.into()))
+}
+
+fn with_account_book_since(context: &Context, tx: TransactionView, since: u64) -> TransactionView {
+    let pos = account_book_input_pos(context, &tx);
+    let mut inputs: Vec<CellInput> = tx.inputs().into_iter().collect();
+    inputs[pos] = inputs[pos].clone().as_builder().since(since.pack()).build();
+    tx.as_advanced_builder().set_inputs(inputs).build()
+}
+
+#[test]
+fn test_close_sale() {
+    // Block 200
+    let sale_end = 200u64;
+    let build = |context: &mut Context, signer: &[u8], since: u64| {
+        let tx = build_update_tx(context, signer, |data| data.paused(1.into()));
+        let tx = update_account_book_cell_data(context, tx, |data| data.sale_end(sale_end.pack()));
+        with_account_book_since(context, tx, since)
+    };
+    let mut context = new_context();
+
+    let tx = build(&mut context, &[0x56; 32], 200);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect("pass");
+
+    let tx = build(&mut context, &[0x56; 32], 199);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("sale not ended");
+}
+
+#[test]
+fn test_update_account_book_without_owner() {
+    let mut context = new_context();
+    let tx = build_update_tx(&mut context, &[0x56; 32], |data| {
+        data.asset_amount(300u128.pack())
+    });
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("owner not found");
+}
+
+#[test]
+fn test_update_account_book_ledger() {
+    let mut context = new_context();
+    // A quota can not be raised
+    let tx = build_update_tx(&mut context, &[0x55; 32], |data| {
+        data.asset_amount(300u128.pack()).a_num(4u32.pack())
+    });
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("quota updated");
+}
+
+#[test]
+fn test_update_account_book_profit() {
+    let mut context = new_context();
+    let tx = build_update_tx(&mut context, &[0x55; 32], |data| {
+        data.a_profit(
+            types::AProfit::new_builder()
+                .set([30u8.into(), 80u8.into()])
+                .build(),
+        )
+    });
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("profit sum");
+}
+
+#[test]
+fn test_update_account_book_after_selling() {
+    let mut context = new_context();
+    let sold = |context: &mut Context, tx| {
+        update_account_book_cell_data(context, tx, |data| {
+            data.member_count(1u32.pack()).a_count(1u32.pack())
+        })
+    };
+
+    let tx = build_update_tx(&mut context, &[0x55; 32], |data| {
+        data.asset_amount(300u128.pack())
+            .platform_id([9u8; 32].pack())
+    });
+    let tx = sold(&mut context, tx);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect("pass");
+
+    let tx = build_update_tx(&mut context, &[0x55; 32], |data| {
+        data.a_profit(
+            types::AProfit::new_builder()
+                .set([100u8.into(), 0u8.into()])
+                .build(),
+        )
+    });
+    let tx = sold(&mut context, tx);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("profit updated");
+}
+
+#[test]
+fn test_update_account_book_close_level() {
+    let mut context = new_context();
+    let sold = |context: &mut Context, tx| {
+        update_account_book_cell_data(context, tx, |data| {
+            data.member_count(1u32.pack()).a_count(1u32.pack())
+        })
+    };
+
+    // Level A is closed at its one member, its pool goes to the Platform
+    let tx = build_update_tx(&mut context, &[0x55; 32], |data| data.a_num(1u32.pack()));
+    let tx = sold(&mut context, tx);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect("pass");
+
+    let tx = build_update_tx(&mut context, &[0x55; 32], |data| data.a_num(0u32.pack()));
+    let tx = sold(&mut context, tx);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("quota below members");
+}
+
+#[test]
+fn test_update_account_book_vault() {
+    let mut context = new_context();
+    let tx = build_update_tx(&mut context, &[0x55; 32], |data| {
+        data.asset_amount(300u128.pack())
+    });
+
+    // The owner takes the vault out
+    let account_book_data = def_account_book_data(&mut context);
+    let account_book_script = build_account_book_script(&mut context, account_book_data);
+    let vault_lock_script = build_input_proxy_script(
+        &mut context,
+        account_book_script.unwrap().calc_script_hash().into(),
+    );
+    let (vault, vault_data) = build_asset_cell(&mut context, vault_lock_script, false, 10000);
+    let vault_input = build_input(context.create_cell(vault, vault_data));
+    let tx = tx.as_advanced_builder().input(vault_input).build();
+    let tx = context.complete_tx(tx);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("vault spent");
+}
+
+// Sells one spore per `dna` in a single transaction, `new_members` is written to the AccountBook.
+// `counts` is the sold count of the levels A~C before this transaction.
+fn build_selling_tx(
+    context: &mut Context,
+    dnas: &[&str],
+    new_members: u32,
+    counts: [u32; 3],
+) -> TransactionView {
+    build_selling_tx_in(context, false, dnas, new_members, counts)
+}
+
+// Sold in xUDT, or in CKB if `ckb`
+fn build_selling_tx_in(
+    context: &mut Context,
+    ckb: bool,
+    dnas: &[&str],
+    new_members: u32,
+    counts: [u32; 3],
+) -> TransactionView {
+    build_selling_tx_with(
+        context,
+        ckb,
+        dnas,
+        new_members,
+        counts,
+        DATA_MIN_CAPACITY,
+        None,
+    )
+}
+
+struct Allowlist {
+    buyers: Vec<Hash>,
+    limit: u32,
+    // Spores the buyer of the selling bought before
+    bought: u32,
+}
+
+fn build_selling_tx_with(
+    context: &mut Context,
+    ckb: bool,
+    dnas: &[&str],
+    new_members: u32,
+    counts: [u32; 3],
+    min_capacity: u64,
+    allowlist: Option<&Allowlist>,
+) -> TransactionView {
+    let def_lock_script: Script = build_always_suc_script(context, &[]);
+    let seller_lock_script: Script = build_always_suc_script(context, &[0x66; 32]);
+    let buyer_lock_script: Script = build_always_suc_script(context, &[0x77; 32]);
+    let (spore_data, cluster_deps) = def_spore(context);
+    let spores_data: Vec<SporeData> = dnas
+        .iter()
+        .map(|dna| {
+            crate::spore::build_serialized_spore_data(
+                format!("{{\"dna\":\"{}\"}}", dna).into_bytes(),
+                "dob/1",
+                Some(get_cluster_id(&spore_data).to_vec()),
+            )
+        })
+        .collect();
+
+    let tx = TransactionBuilder::default().build();
+
+    // Account Book
+    let account_book_data = def_account_book_data(context);
+    let account_book_data = account_book_data
+        .as_builder()
+        .xudt_script_hash(build_asset_script_hash(context, ckb).pack())
+        .cluster_id(get_cluster_id(&spore_data).pack())
+        .build();
+    let mut new_counts = counts;
+    for spore_data in &spores_data {
+        let level = Level::from_spore(spore_data, DNA_LEVEL_OFFSET, DNA_LEVEL_WIDTH).unwrap();
+        if let Some(count) = new_counts.get_mut(level as usize - 1) {
+            *count += 1;
+        }
+    }
+    let ab_cell_data = def_account_book_cell_data(context)
+        .as_builder()
+        .member_count(35u32.pack())
+        .a_count(counts[0].pack())
+        .b_count(counts[1].pack())
+        .c_count(counts[2].pack())
+        .a_share(
+            Share {
+                index: 4600,
+                pool: 0,
+            }
+            .into(),
+        )
+        .b_share(
+            Share {
+                index: 70,
+                pool: 1000,
+            }
+            .into(),
+        )
+        .build();
+    let ab_cell_data_new = ab_cell_data
+        .clone()
+        .as_builder()
+        .member_count((35 + new_members).pack())
+        .a_count(new_counts[0].pack())
+        .b_count(new_counts[1].pack())
+        .c_count(new_counts[2].pack())
+        .build();
+
+    let tx = build_account_book(
+        context,
+        tx,
+        account_book_data.clone(),
+        (ab_cell_data, ab_cell_data_new),
+        (10000, 10000 + DATA_ASSET_AMOUNT * dnas.len() as u128),
+    );
+    let account_book_script_hash = get_account_script_hash(account_book_data);
+
+    let mut tx = tx;
+    for spore_data in &spores_data {
+        // DOB Selling
+        let dob_selling_data = def_dob_selling_data(context, spore_data)
+            .as_builder()
+            .account_book_script_hash(account_book_script_hash.pack())
+            .build();
+        let cell_input_dob_selling = {
+            let dob_selling = build_dob_selling_script(context, &dob_selling_data);
+            let (dob_selling_udt, dob_selling_udt_data) =
+                build_asset_cell(context, dob_selling, ckb, DATA_ASSET_AMOUNT);
+
+            CellInput::new_builder()
+                .previous_output(context.create_cell(dob_selling_udt, dob_selling_udt_data))
+                .build()
+        };
+        // In CKB the output is counted, so it holds at least the occupied capacity
+        let seller_output = if ckb {
+            build_asset_cell(context, seller_lock_script.clone(), true, 0).0
+        } else {
+            CellOutput::new_builder()
+                .lock(seller_lock_script.clone())
+                .capacity(1000.pack())
+                .build()
+        };
+        tx = tx
+            .as_advanced_builder()
+            .input(cell_input_dob_selling)
+            .output(seller_output)
+            .output_data(Default::default())
+            .witness(
+                WitnessArgs::new_builder()
+                    .lock(Some(dob_selling_data.as_bytes()).pack())
+                    .build()
+                    .as_bytes()
+                    .pack(),
+            )
+            .build();
+
+        // Buy Intent, the spore is minted to the buyer
+        let buy_intent_data = def_buy_intent_data(context, &dob_selling_data)
+            .as_builder()
+            .xudt_script_hash(build_asset_script_hash(context, ckb).pack())
+            .owner_script_hash(buyer_lock_script.calc_script_hash())
+            .min_capacity(min_capacity.pack())
+            .build();
+        let cell_input_buy_intent = {
+            let buy_intent_script = build_buy_intent_cell(
+                context,
+                DATA_MIN_CAPACITY,
+                def_lock_script.clone(),
+                &[
+                    account_book_script_hash,
+                    ckb_hash(buy_intent_data.as_slice()),
+                ]
+                .concat(),
+            );
+
+            CellInput::new_builder()
+                .previous_output(context.create_cell(buy_intent_script.clone(), Default::default()))
+                .build()
+        };
+
+        tx = tx
+            .as_advanced_builder()
+            .input(cell_input_buy_intent)
+            .witness(
+                WitnessArgs::new_builder()
+                    .input_type(Some(buy_intent_data.as_bytes()).pack())
+                    .build()
+                    .as_bytes()
+                    .pack(),
+            )
+            .build();
+    }
+
+    // Spore
+    let buyer: Hash = buyer_lock_script.calc_script_hash().into();
+    let tx = build_mint_spores(context, tx, cluster_deps, spores_data, buyer_lock_script);
+    // Each spore takes the capacity of its BuyIntent
+    let outputs: Vec<CellOutput> = tx
+        .outputs()
+        .into_iter()
+        .map(|output| match output.type_().to_opt() {
+            Some(t) if t.code_hash().as_slice() == *SporeCodeHash => output
+                .as_builder()
+                .capacity(DATA_MIN_CAPACITY.pack())
+                .build(),
+            _ => output,
+        })
+        .collect();
+    let tx = tx.as_advanced_builder().set_outputs(outputs).build();
+
+    let mut buyers = Vec::new();
+    let tx = match allowlist {
+        Some(allowlist) => {
+            let smt = AccountBook::new_allowlist(&allowlist.buyers);
+            let witness = types::AllowlistWitness::new_builder()
+                .proof(smt.keys_proof(&[SmtKey::Buyer(buyer.clone())]).pack())
+                .buyers(
+                    types::BuyerCountVec::new_builder()
+                        .push(
+                            types::BuyerCount::new_builder()
+                                .owner_script_hash(buyer.clone().into())
+                                .count(allowlist.bought.pack())
+                                .build(),
+                        )
+                        .build(),
+                )
+                .build();
+            buyers.push((
+                buyer,
+                allowlist.bought,
+                allowlist.bought + dnas.len() as u32,
+            ));
+
+            let tx = update_account_book_cell_data(context, tx, |data| {
+                data.allowlist_root(smt.root_hash().into())
+                    .allowlist_limit(allowlist.limit.pack())
+            });
+            let pos = account_book_input_pos(context, &tx);
+            let mut witnesses: Vec<Bytes> = tx.witnesses().into_iter().collect();
+            witnesses[pos] = WitnessArgs::new_unchecked(witnesses[pos].raw_data())
+                .as_builder()
+                .input_type(Some(witness.as_bytes()).pack())
+                .build()
+                .as_bytes()
+                .pack();
+            tx.as_advanced_builder().set_witnesses(witnesses).build()
+        }
+        None => tx,
+    };
+
+    let tx = update_accountbook(
+        context,
+        tx,
+        DATA_ASSET_AMOUNT,
+        (DNA_LEVEL_OFFSET, DNA_LEVEL_WIDTH),
+        TotalAmounts {
+            a: 5000,
+            b: 5000,
+            c: 0,
+            d: 0,
+        },
+        &buyers,
+    );
+    context.complete_tx(tx)
+}
+
+#[test]
+fn test_simple_selling() {
+    let mut context = new_context();
+    let tx = build_selling_tx(&mut context, &["40000000000002"], 1, [2, 10, 20]);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect("pass");
+}
+
+#[test]
+fn test_batch_selling() {
+    let mut context = new_context();
+    let tx = build_selling_tx(
+        &mut context,
+        &["40000000000002", "40000000000001", "40000000000003"],
+        3,
+        [2, 10, 20],
+    );
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect("pass");
+}
+
+#[test]
+fn test_selling_ckb() {
+    let mut context = new_context();
+    let tx = build_selling_tx_in(
+        &mut context,
+        true,
+        &["40000000000002", "40000000000003"],
+        2,
+        [2, 10, 20],
+    );
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect("pass");
+}
+
+#[test]
+fn test_selling_ckb_vault_underpaid() {
+    let mut context = new_context();
+    let tx = build_selling_tx_in(&mut context, true, &["40000000000002"], 1, [2, 10, 20]);
+
+    // The vault keeps one shannon of the payment out
+    let outputs: Vec<CellOutput> = tx
+        .outputs()
+        .into_iter()
+        .map(|output| {
+            if output.lock().code_hash().as_slice() == *InputTypeProxyLockCodeHash {
+                let capacity: u64 = output.capacity().unpack();
+                output.as_builder().capacity((capacity - 1).pack()).build()
+            } else {
+                output
+            }
+        })
+        .collect();
+    let tx = tx.as_advanced_builder().set_outputs(outputs).build();
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("vault underpaid");
+}
+
+#[test]
+fn test_selling_xudt_extension() {
+    let mut context = new_context();
+    let tx = build_selling_tx(&mut context, &["40000000000002"], 1, [2, 10, 20]);
+    let tx = with_xudt_extension(&mut context, tx, &[0xEE; 20]);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect("pass");
+}
+
+#[test]
+fn test_batch_selling_member_count() {
+    let mut context = new_context();
+    let tx = build_selling_tx(
+        &mut context,
+        &["40000000000002", "40000000000001"],
+        1,
+        [2, 10, 20],
+    );
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("member count");
+}
+
+#[test]
+fn test_selling_without_buy_intent() {
+    let mut context = new_context();
+    let tx = build_selling_tx(&mut context, &["40000000000002"], 1, [2, 10, 20]);
+
+    let pos = tx
+        .inputs()
+        .into_iter()
+        .position(|input| {
+            let (output, _) = context.get_cell(&input.previous_output()).unwrap();
+            output
+                .type_()
+                .to_opt()
+                .is_some_and(|t| t.code_hash().as_slice() == *BuyIntentCodeHash)
+        })
+        .unwrap();
+    let inputs: Vec<CellInput> = tx
+        .inputs()
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, input)| (i != pos).then_some(input))
+        .collect();
+    let witnesses: Vec<_> = tx
+        .witnesses()
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, witness)| (i != pos).then_some(witness))
+        .collect();
+    let tx = tx
+        .as_advanced_builder()
+        .set_inputs(inputs)
+        .set_witnesses(witnesses)
+        .build();
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("no BuyIntent");
+}
+
+#[test]
+fn test_selling_forged_spore() {
+    let mut context = new_context();
+    let tx = build_selling_tx(&mut context, &["40000000000002"], 1, [2, 10, 20]);
+
+    // A cell with the spore data and id, but not the spore type script
+    let outputs: Vec<CellOutput> = tx
+        .outputs()
+        .into_iter()
+        .map(|output| match output.type_().to_opt() {
+            Some(t) if t.code_hash().as_slice() == *SporeCodeHash => {
+                let forged = build_always_suc_script(&mut context, &t.args().raw_data());
+                output.as_builder().type_(Some(forged).pack()).build()
+            }
+            _ => output,
+        })
+        .collect();
+    let tx = tx.as_advanced_builder().set_outputs(outputs).build();
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("forged spore");
+}
+
+#[test]
+fn test_selling_spore_not_sold() {
+    let mut context = new_context();
+    let tx = build_selling_tx(
+        &mut context,
+        &["40000000000002", "40000000000003"],
+        2,
+        [2, 10, 20],
+    );
+
+    // The second DobSelling and its BuyIntent are dropped, a user pays the vault instead
+    let inputs: Vec<CellInput> = tx.inputs().into_iter().take(4).collect();
+    let mut witnesses: Vec<Bytes> = tx.witnesses().into_iter().collect();
+    witnesses.drain(4..6);
+    let user_lock_script = build_user1_script(&mut context);
+    let user_xudt = build_xudt_cell(&mut context, user_lock_script)
+        .as_builder()
+        .capacity((DATA_MIN_CAPACITY * 2).pack())
+        .build();
+    let user_input = build_input(
+        context.create_cell(user_xudt, DATA_ASSET_AMOUNT.to_le_bytes().to_vec().into()),
+    );
+    let tx = tx
+        .as_advanced_builder()
+        .set_inputs(inputs)
+        .input(user_input)
+        .set_witnesses(witnesses)
+        .build();
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("spore not sold");
+}
+
+#[test]
+fn test_selling_with_withdrawal_intent() {
+    let mut context = new_context();
+    let tx = build_selling_tx(&mut context, &["40000000000002"], 1, [2, 10, 20]);
+
+    // A WithdrawalIntent of the AccountBook is consumed by the selling
+    let pos = account_book_input_pos(&context, &tx);
+    let account_book_script_hash: Hash = tx
+        .outputs()
+        .get(pos)
+        .unwrap()
+        .type_()
+        .to_opt()
+        .unwrap()
+        .calc_script_hash()
+        .into();
+    let withdrawal_intent_data = def_withdrawal_intent_data(&mut context);
+    let withdrawal_intent_script = build_withdrawal_intent_script(
+        &mut context,
+        &withdrawal_intent_data,
+        account_book_script_hash,
+    );
+    let def_lock_script = build_always_suc_script(&mut context, &[]);
+    let input = build_input(
+        context.create_cell(
+            CellOutput::new_builder()
+                .capacity(1000.pack())
+                .lock(def_lock_script)
+                .type_(withdrawal_intent_script.pack())
+                .build(),
+            Default::default(),
+        ),
+    );
+
+    // Its witness is at the index of the new input
+    let mut witnesses: Vec<Bytes> = tx.witnesses().into_iter().collect();
+    witnesses.insert(
+        tx.inputs().len(),
+        WitnessArgs::new_builder()
+            .input_type(Some(withdrawal_intent_data.as_bytes()).pack())
+            .build()
+            .as_bytes()
+            .pack(),
+    );
+    let tx = tx
+        .as_advanced_builder()
+        .input(input)
+        .set_witnesses(witnesses)
+        .build();
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("WithdrawalIntent burned");
+}
+
+#[test]
+fn test_selling_capacity_not_returned() {
+    let mut context = new_context();
+    let tx = build_selling_tx(&mut context, &["40000000000002"], 1, [2, 10, 20]);
+
+    // The spore cell takes less than the BuyIntent held, the rest is not returned
+    let outputs: Vec<CellOutput> = tx
+        .outputs()
+        .into_iter()
+        .map(|output| match output.type_().to_opt() {
+            Some(t) if t.code_hash().as_slice() == *SporeCodeHash => output
+                .as_builder()
+                .capacity((DATA_MIN_CAPACITY - 1).pack())
+                .build(),
+            _ => output,
+        })
+        .collect();
+    let tx = tx.as_advanced_builder().set_outputs(outputs).build();
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("capacity not returned");
+}
+
+#[test]
+fn test_selling_min_capacity() {
+    // 100 CKB is reserved for the spore, but it occupies more
+    let mut context = new_context();
+    let tx = build_selling_tx_with(
+        &mut context,
+        false,
+        &["40000000000002"],
+        1,
+        [2, 10, 20],
+        100_0000_0000,
+        None,
+    );
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("min capacity too low");
+}
+
+#[test]
+fn test_selling_shares_modified() {
+    let mut context = new_context();
+    let tx = build_selling_tx(&mut context, &["40000000000002"], 1, [2, 10, 20]);
+
+    // The members of level A take one more than the selling raised
+    let pos = account_book_input_pos(&context, &tx);
+    let mut outputs_data: Vec<Bytes> = tx.outputs_data().into_iter().collect();
+    let cell_data = AccountBookCellData::new_unchecked(outputs_data[pos].raw_data());
+    let share = Share::from(cell_data.a_share());
+    outputs_data[pos] = cell_data
+        .as_builder()
+        .a_share(
+            Share {
+                index: share.index + 1,
+                ..share
+            }
+            .into(),
+        )
+        .build()
+        .as_slice()
+        .pack();
+    let tx = tx
+        .as_advanced_builder()
+        .set_outputs_data(outputs_data)
+        .build();
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("shares modified");
+}
+
+#[test]
+fn test_selling_paused() {
+    let mut context = new_context();
+    let tx = build_selling_tx(&mut context, &["40000000000002"], 1, [2, 10, 20]);
+    let tx = pause_account_book(&mut context, tx);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("paused");
+}
+
+#[test]
+fn test_selling_sale_window() {
+    // From block 100 to block 200
+    let build = |context: &mut Context, since: u64| {
+        let tx = build_selling_tx(context, &["40000000000002"], 1, [2, 10, 20]);
+        let tx = update_account_book_cell_data(context, tx, |data| {
+            data.sale_start(100u64.pack()).sale_end(200u64.pack())
+        });
+        with_account_book_since(context, tx, since)
+    };
+    let mut context = new_context();
+
+    let tx = build(&mut context, 150);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect("pass");
+
+    let tx = build(&mut context, 0);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("not started");
+
+    let tx = build(&mut context, 200);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("ended");
+
+    // Timestamp does not compare with block number
+    let tx = build(&mut context, 0x4000_0000_0000_0096);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("metric");
+}
+
+#[test]
+fn test_selling_allowlist() {
+    let buyer = build_always_suc_script(&mut new_context(), &[0x77; 32]);
+    let buyer: Hash = buyer.calc_script_hash().into();
+    let allowlist = |buyers: Vec<Hash>, limit: u32, bought: u32| Allowlist {
+        buyers,
+        limit,
+        bought,
+    };
+    let build = |context: &mut Context, allowlist: Allowlist| {
+        build_selling_tx_with(
+            context,
+            false,
+            &["40000000000002", "40000000000003"],
+            2,
+            [2, 10, 20],
+            DATA_MIN_CAPACITY,
+            Some(&allowlist),
+        )
+    };
+    let mut context = new_context();
+
+    let tx = build(
+        &mut context,
+        allowlist(vec![[0x12; 32].into(), buyer.clone()], 3, 1),
+    );
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect("pass");
+
+    let tx = build(&mut context, allowlist(vec![buyer.clone()], 0, 100));
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect("no limit");
+
+    let tx = build(&mut context, allowlist(vec![[0x12; 32].into()], 3, 0));
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("not in allowlist");
+
+    let tx = build(&mut context, allowlist(vec![buyer], 3, 2));
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("limit exceeded");
+}
+
+#[test]
+fn test_selling_level_sold_out() {
+    let mut context = new_context();
+    let tx = build_selling_tx(&mut context, &["40000000000001"], 1, [3, 10, 20]);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("level A sold out");
+}
+
+#[test]
+fn test_dob_dna_level() {
+    use utils::dob::decode_dna;
+
+    let level = |content: &str| {
+        decode_dna(content.as_bytes())
+            .and_then(|dna| Level::from_dna(&dna, DNA_LEVEL_OFFSET, DNA_LEVEL_WIDTH))
+            .ok()
+    };
+
+    assert_eq!(level("{\"dna\":\"40000000000002\"}"), Some(Level::B));
+    assert_eq!(level("\"40000000000003\""), Some(Level::C));
+    assert_eq!(level("[\"40000000000004\", 1]"), Some(Level::D));
+    // Other fields do not affect the level
+    assert_eq!(
+        level("{ \"dna\": \"40000000000001\", \"id\": \"f\", \"n\": [1, {\"x\": null}] }"),
+        Some(Level::A)
+    );
+    assert_eq!(
+        level("{\"id\":\"02\",\"dna\":\"40000000000001\"}"),
+        Some(Level::A)
+    );
+
+    assert_eq!(level("{\"dna\":\"40000000000005\"}"), None);
+    assert_eq!(level("{\"dna\":\"400000000002\"}"), None);
+    assert_eq!(level("{\"dna\":\"4000000000002\"}"), None);
+    assert_eq!(level("{\"dna\":\"4000000000000g\"}"), None);
+    assert_eq!(level("{\"id\":\"40000000000002\"}"), None);
+    assert_eq!(
+        level("{\"dna\":\"40000000000002\",\"dna\":\"40000000000001\"}"),
+        None
+    );
+    assert_eq!(level("{\"dna\":\"40000000000002\"} 1"), None);
+    assert_eq!(level("{\"dna\":\"40000000000002\""), None);
+}
+
+// Creates a WithdrawalIntent of `owner` for the spore held by the default lock.
+fn build_withdrawal_intent_tx(context: &mut Context, owner: &Script) -> TransactionView {
+    // Add Spore
+    let tx = TransactionBuilder::default().build();
+    let def_lock_script = build_always_suc_script(context, &[]);
+
+    let (spore_data, _cluster_dep) = def_spore(context);
+    let tx = build_transfer_spore(context, tx, &spore_data);
+    let tx = context.complete_tx(tx);
+
+    let withdrawal_intent_data = def_withdrawal_intent_data(context)
+        .as_builder()
+        .spore_id(get_spore_id(&tx).pack())
+        .spore_level(2.into())
+        .cluster_id(get_cluster_id(&spore_data).pack())
+        .owner_script_hash(owner.calc_script_hash())
+        .build();
+    let withdrawal_intent_script =
+        build_withdrawal_intent_script(context, &withdrawal_intent_data, [0u8; 32].into());
+    // Inputs: CKB + Spore
+    // Output: Withdrawal intent + Spore
+    let tx = tx
+        .as_advanced_builder()
+        .input(build_input(build_out_point1(
+            context,
+            def_lock_script.clone(),
+        )))
+        .output(
+            CellOutput::new_builder()
+                .lock(def_lock_script)
+                .type_(withdrawal_intent_script.pack())
+                .capacity(1000.pack())
+                .build(),
+        )
+        .output_data(Default::default())
+        .witness(
+            WitnessArgs::new_builder()
+                .output_type(Some(withdrawal_intent_data.as_bytes()).pack())
+                .build()
+                .as_slice()
+                .pack(),
+        )
+        .build();
+
+    context.complete_tx(tx)
+}
+
+#[test]
+fn test_simple_withdrawal_intent() {
+    let mut context = new_context();
+    let owner = build_always_suc_script(&mut context, &[]);
+    let tx = build_withdrawal_intent_tx(&mut context, &owner);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect("pass");
+}
+
+#[test]
+fn test_withdrawal_intent_not_spore_owner() {
+    let mut context = new_context();
+    let owner = build_always_suc_script(&mut context, &[0x22; 32]);
+    let tx = build_withdrawal_intent_tx(&mut context, &owner);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("spore held by another lock");
+}
+
+#[test]
+fn test_withdrawal_intent_spore_burned() {
+    let mut context = new_context();
+    let owner = build_always_suc_script(&mut context, &[]);
+    let tx = build_withdrawal_intent_tx(&mut context, &owner);
+
+    // The output spore keeps the data and lock, but not the spore type
+    let mut outputs: Vec<CellOutput> = tx.outputs().into_iter().collect();
+    outputs[0] = outputs[0].clone().as_builder().type_(None.pack()).build();
+    let tx = tx.as_advanced_builder().set_outputs(outputs).build();
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("spore burned");
+}
+
+#[test]
+fn test_withdrawal_intent_forged_spore() {
+    let mut context = new_context();
+    let owner = build_always_suc_script(&mut context, &[]);
+    let tx = build_withdrawal_intent_tx(&mut context, &owner);
+
+    // A cell of another type with the spore id as args and the same spore data
+    let spore_id = get_spore_id(&tx);
+    let forged_type = build_always_suc_script(&mut context, &spore_id);
+    let forged = tx
+        .output(0)
+        .unwrap()
+        .as_builder()
+        .type_(Some(forged_type).pack())
+        .build();
+    let forged_input = CellInput::new_builder()
+        .previous_output(
+            context.create_cell(forged.clone(), tx.outputs_data().get(0).unwrap().raw_data()),
+        )
+        .build();
+    let mut inputs: Vec<CellInput> = tx.inputs().into_iter().collect();
+    inputs[0] = forged_input;
+    let mut outputs: Vec<CellOutput> = tx.outputs().into_iter().collect();
+    outputs[0] = forged;
+    let tx = tx
+        .as_advanced_builder()
+        .set_inputs(inputs)
+        .set_outputs(outputs)
+        .build();
+    let tx = context.complete_tx(tx);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("forged spore");
+}
+
+// Revokes a WithdrawalIntent, which expires at block 1000, with the input `since`.
+// The owner gets `capacity` back.
+fn build_revocation_withdrawal_intent_tx(
+    context: &mut Context,
+    since: u64,
+    capacity: u64,
+) -> TransactionView {
+    let owner_lock_script = build_always_suc_script(context, &[0x22; 32]);
+    let withdrawal_intent_data = def_withdrawal_intent_data(context)
+        .as_builder()
+        .spore_level(2.into())
+        .owner_script_hash(owner_lock_script.calc_script_hash())
+        .build();
+    let withdrawal_intent_script =
+        build_withdrawal_intent_script(context, &withdrawal_intent_data, [0u8; 32].into());
+    let cell_input_withdrawal_intent = CellInput::new_builder()
+        .previous_output(
+            context.create_cell(
+                CellOutput::new_builder()
+                    .lock(owner_lock_script.clone())
+                    .type_(withdrawal_intent_script.pack())
+                    .capacity(1000.pack())
+                    .build(),
+                Default::default(),
+            ),
+        )
+        .since(since.pack())
+        .build();
+
+    let tx = TransactionBuilder::default()
+        .input(cell_input_withdrawal_intent)
+        .output(
+            CellOutput::new_builder()
+                .lock(owner_lock_script)
+                .capacity(capacity.pack())
+                .build(),
+        )
+        .output_data(Default::default())
+        .witness(
+            WitnessArgs::new_builder()
+                .input_type(Some(withdrawal_intent_data.as_bytes()).pack())
+                .build()
+                .as_bytes()
+                .pack(),
+        )
+        .build();
+    context.complete_tx(tx)
+}
+
+#[test]
+fn test_revocation_withdrawal_intent() {
+    let mut context = new_context();
+    let tx = build_revocation_withdrawal_intent_tx(&mut context, 10000, 1000);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect("pass");
+}
+
+#[test]
+fn test_revocation_withdrawal_intent_not_expired() {
+    let mut context = new_context();
+    let tx = build_revocation_withdrawal_intent_tx(&mut context, 999, 1000);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("block 999 < 1000");
+}
+
+#[test]
+fn test_revocation_withdrawal_intent_capacity() {
+    let mut context = new_context();
+    let tx = build_revocation_withdrawal_intent_tx(&mut context, 10000, 999);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("capacity not returned");
+}
+
+// AccountBook whose leaves `(key, old_value, amount)` pay out `amount` more each from the vault.
+fn build_account_book_withdrawal(
+    context: &mut Context,
+    ckb: bool,
+    account_book_cell_data: AccountBookCellData,
+    leaves: &[(SmtKey, SmtValue, u128)],
+) -> (TransactionView, Hash) {
+    let tx = TransactionBuilder::default().build();
+    let def_lock_script = build_always_suc_script(context, &[0x11; 32]);
+
+    let mut smt = AccountBook::new_test();
+    let totals = smt.get_total();
+    for (key, old_value, _) in leaves {
+        smt.update(key.clone(), old_value.clone());
+    }
+    let old_hash = smt.root_hash();
+    for (key, old_value, amount) in leaves {
+        smt.update(
+            key.clone(),
+            old_value.with_amount(old_value.amount() + amount),
+        );
+    }
+    let new_hash = smt.root_hash();
+    let keys: Vec<SmtKey> = leaves.iter().map(|(key, _, _)| key.clone()).collect();
+    let proof = smt.multi_proof(&keys);
+    let amount: u128 = leaves.iter().map(|(_, _, amount)| amount).sum();
+    let member_leaves = MemberLeafVec::new_builder()
+        .set(
+            leaves
+                .iter()
+                .filter_map(|(_, value, _)| match value {
+                    SmtValue::Member(leaf) => Some(leaf.clone()),
+                    _ => None,
+                })
+                .collect(),
+        )
+        .build();
+
+    // Account Book
+    let account_book_data = def_account_book_data(context)
+        .as_builder()
+        .xudt_script_hash(build_asset_script_hash(context, ckb).pack())
+        .proof(proof.pack())
+        .total_a(totals.a.pack())
+        .total_b(totals.b.pack())
+        .total_c(totals.c.pack())
+        .total_d(totals.d.pack())
         .build();
 
-    let account_book_script = build_account_book_script(&mut context, account_book_data.clone());
+    let account_book_script = build_account_book_script(context, account_book_data.clone());
     let tx = {
         let input_proxy_script = build_input_proxy_script(
-            &mut context,
+            context,
             account_book_script
                 .as_ref()
                 .unwrap()
@@ -464,32 +1854,28 @@ fn test_simple_withdrawal_suc() {
         );
 
         let input_cell = {
-            context.create_cell(
-                CellOutput::new_builder()
-                    .capacity(16.pack())
-                    .lock(input_proxy_script.clone())
-                    .type_(xudt_script.clone().pack())
-                    .build(),
-                10000u128.to_le_bytes().to_vec().into(),
-            )
-        };
-        let output_cell = {
-            CellOutput::new_builder()
-                .capacity(16.pack())
-                .lock(input_proxy_script.clone())
-                .type_(xudt_script.pack())
-                .build()
+            let (cell, data) = build_asset_cell(
+                context,
+                input_proxy_script.clone(),
+                ckb,
+                totals.total().unwrap(),
+            );
+            context.create_cell(cell, data)
         };
+        let (output_cell, output_data) = build_asset_cell(
+            context,
+            input_proxy_script.clone(),
+            ckb,
+            totals.total().unwrap() - amount,
+        );
         tx.as_advanced_builder()
             .input(build_input(input_cell))
             .output(output_cell)
-            .output_data((10000u128 - 200).to_le_bytes().pack())
+            .output_data(output_data.pack())
             .witness(Default::default())
             .build()
     };
     let tx = {
-        // TODO Build SMT Info
-
         let input_cell = {
             context.create_cell(
                 CellOutput::new_builder()
@@ -497,7 +1883,12 @@ fn test_simple_withdrawal_suc() {
                     .lock(def_lock_script.clone())
                     .type_(account_book_script.clone().pack())
                     .build(),
-                account_book_cell_data.as_bytes().into(),
+                account_book_cell_data
+                    .clone()
+                    .as_builder()
+                    .smt_root_hash(old_hash.into())
+                    .build()
+                    .as_bytes(),
             )
         };
         let output_cell = {
@@ -510,9 +1901,17 @@ fn test_simple_withdrawal_suc() {
         tx.as_advanced_builder()
             .input(build_input(input_cell))
             .output(output_cell)
-            .output_data(account_book_cell_data.as_slice().pack())
+            .output_data(
+                account_book_cell_data
+                    .as_builder()
+                    .smt_root_hash(new_hash.into())
+                    .build()
+                    .as_slice()
+                    .pack(),
+            )
             .witness(
                 WitnessArgs::new_builder()
+                    .input_type(Some(member_leaves.as_bytes()).pack())
                     .output_type(Some(account_book_data.as_bytes()).pack())
                     .build()
                     .as_bytes()
@@ -521,45 +1920,120 @@ fn test_simple_withdrawal_suc() {
             .build()
     };
 
-    // Withdrawal Intent
-    let tx = {
-        let withdrawal_intent_data = def_withdrawal_intent_data(&mut context)
+    (
+        tx,
+        account_book_script
+            .as_ref()
+            .unwrap()
+            .calc_script_hash()
+            .into(),
+    )
+}
+
+// The shares of the totals in `AccountBook::new_test`, with every level full
+fn withdrawal_account_book_cell_data(context: &mut Context) -> AccountBookCellData {
+    def_account_book_cell_data(context)
+        .as_builder()
+        .a_count(3u32.pack())
+        .b_count(17u32.pack())
+        .c_count(25u32.pack())
+        // (20000 * 60 + 5000 * 36) / (100 * 3)
+        .a_share(
+            Share {
+                index: 4600,
+                pool: 0,
+            }
+            .into(),
+        )
+        // 5000 * 24 / (100 * 17), with 5000 * 24 % (100 * 17) left in the pool
+        .b_share(
+            Share {
+                index: 70,
+                pool: 1000,
+            }
+            .into(),
+        )
+        .build()
+}
+
+// Members `(level, old_amount, amount)` who have already withdrawn `old_amount`, withdrawing `amount` more.
+fn build_withdrawal_tx(context: &mut Context, members: &[(u8, u128, u128)]) -> TransactionView {
+    build_withdrawal_tx_in(context, false, members)
+}
+
+// Paid out in xUDT, or in CKB if `ckb`
+fn build_withdrawal_tx_in(
+    context: &mut Context,
+    ckb: bool,
+    members: &[(u8, u128, u128)],
+) -> TransactionView {
+    let def_lock_script = build_always_suc_script(context, &[0x11; 32]);
+    let owner_lock_script = build_always_suc_script(context, &[0x22; 32]);
+
+    let spore_ids: Vec<Hash> = (0..members.len())
+        .map(|i| [0x1B + i as u8; 32].into())
+        .collect();
+    let cluster_id: Hash = [0x1A; 32].into();
+
+    let account_book_cell_data = withdrawal_account_book_cell_data(context);
+    let leaves: Vec<(SmtKey, SmtValue, u128)> = spore_ids
+        .iter()
+        .zip(members)
+        .enumerate()
+        .map(|(i, (spore_id, (level, old_amount, amount)))| {
+            (
+                SmtKey::Member(spore_id.clone()),
+                SmtValue::new_member((*level).try_into().unwrap(), *old_amount, i as u32, 0),
+                *amount,
+            )
+        })
+        .collect();
+    let (mut tx, account_book_script_hash) =
+        build_account_book_withdrawal(context, ckb, account_book_cell_data, &leaves);
+
+    // Withdrawal Intents, each paid at its change location, which returns the capacity of the
+    // intent. In CKB the payout is the capacity beyond it.
+    let intent_capacity = 200_0000_0000u64;
+    for (spore_id, (level, _, amount)) in spore_ids.into_iter().zip(members) {
+        let withdrawal_intent_data = def_withdrawal_intent_data(context)
             .as_builder()
+            .xudt_script_hash(build_asset_script_hash(context, ckb).pack())
+            .spore_level((*level).into())
             .spore_id(spore_id.into())
-            .cluster_id(cluster_id.into())
+            .cluster_id(cluster_id.clone().into())
+            .owner_script_hash(owner_lock_script.calc_script_hash())
+            .change_location((tx.outputs().len() as u32).pack())
             .build();
         let withdrawal_intent_script = build_withdrawal_intent_script(
-            &mut context,
+            context,
             &withdrawal_intent_data,
-            account_book_script
-                .as_ref()
-                .unwrap()
-                .calc_script_hash()
-                .into(),
+            account_book_script_hash.clone(),
         );
 
         let input_cell = {
             context.create_cell(
                 CellOutput::new_builder()
-                    .capacity(16.pack())
+                    .capacity(intent_capacity.pack())
                     .lock(def_lock_script.clone())
                     .type_(withdrawal_intent_script.pack())
                     .build(),
                 Default::default(),
             )
         };
-        let output_cell = {
-            CellOutput::new_builder()
-                .capacity(16.pack())
-                .lock(def_lock_script.clone())
-                .type_(xudt_script.pack())
-                .build()
+        let (output_cell, output_data) =
+            build_asset_cell(context, owner_lock_script.clone(), ckb, *amount);
+        let capacity = if ckb {
+            intent_capacity + *amount as u64
+        } else {
+            intent_capacity
         };
+        let output_cell = output_cell.as_builder().capacity(capacity.pack()).build();
 
-        tx.as_advanced_builder()
+        tx = tx
+            .as_advanced_builder()
             .input(build_input(input_cell))
             .output(output_cell)
-            .output_data(200u128.to_le_bytes().pack())
+            .output_data(output_data.pack())
             .witness(
                 WitnessArgs::new_builder()
                     .input_type(Some(withdrawal_intent_data.as_bytes()).pack())
@@ -567,9 +2041,297 @@ fn test_simple_withdrawal_suc() {
                     .as_bytes()
                     .pack(),
             )
-            .build()
+            .build();
+    }
+
+    context.complete_tx(tx)
+}
+
+#[test]
+fn test_simple_withdrawal_suc() {
+    let mut context = new_context();
+    // (20000 * 60 + 5000 * 36) / (100 * 3)
+    let tx = build_withdrawal_tx(&mut context, &[(1, 10, 4600 - 10)]);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect("pass");
+}
+
+#[test]
+fn test_withdrawal_ckb() {
+    let mut context = new_context();
+    let tx = build_withdrawal_tx_in(&mut context, true, &[(1, 10, 4590), (2, 0, 70)]);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect("pass");
+}
+
+#[test]
+fn test_withdrawal_ckb_exceeds_entitlement() {
+    let mut context = new_context();
+    let tx = build_withdrawal_tx_in(&mut context, true, &[(1, 10, 4591)]);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("withdrawal exceeds");
+}
+
+#[test]
+fn test_withdrawal_capacity_not_returned() {
+    let mut context = new_context();
+    let tx = build_withdrawal_tx(&mut context, &[(1, 10, 4590), (2, 0, 70)]);
+
+    // The payout of the second intent returns one shannon less than the intent held
+    let mut outputs: Vec<CellOutput> = tx.outputs().into_iter().collect();
+    let capacity: u64 = outputs[3].capacity().unpack();
+    outputs[3] = outputs[3]
+        .clone()
+        .as_builder()
+        .capacity((capacity - 1).pack())
+        .build();
+    let tx = tx.as_advanced_builder().set_outputs(outputs).build();
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("capacity not returned");
+}
+
+#[test]
+fn test_withdrawal_paused() {
+    let mut context = new_context();
+    let tx = build_withdrawal_tx(&mut context, &[(1, 10, 4600 - 10)]);
+    let tx = pause_account_book(&mut context, tx);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect("pass");
+}
+
+#[test]
+fn test_withdrawal_exceeds_entitlement() {
+    let mut context = new_context();
+    let tx = build_withdrawal_tx(&mut context, &[(1, 10, 4600 - 9)]);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("withdrawal exceeds");
+}
+
+#[test]
+fn test_batch_withdrawal() {
+    let mut context = new_context();
+    // Level A: 4600, Level B: (5000 * 24) / (100 * 17)
+    let tx = build_withdrawal_tx(&mut context, &[(1, 10, 4590), (2, 0, 70), (1, 0, 4600)]);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect("pass");
+}
+
+#[test]
+fn test_batch_withdrawal_exceeds_entitlement() {
+    let mut context = new_context();
+    let tx = build_withdrawal_tx(&mut context, &[(1, 10, 4590), (2, 0, 71)]);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("withdrawal exceeds");
+}
+
+#[test]
+fn test_profit_distribution() {
+    let mut context = new_context();
+    let config = ProfitConfig::from(&withdrawal_account_book_cell_data(&mut context));
+
+    let totals = AccountBook::new_test().get_total();
+    assert_eq!(config.get_withdrawal(Level::A, 0, &totals).unwrap(), 4600);
+    assert_eq!(
+        config.get_withdrawal(Level::A, 1000, &totals).unwrap(),
+        3600
+    );
+    assert_eq!(config.get_withdrawal(Level::B, 0, &totals).unwrap(), 70);
+    assert_eq!(config.get_withdrawal(Level::C, 0, &totals).unwrap(), 0);
+    assert_eq!(config.get_withdrawal(Level::D, 0, &totals).unwrap(), 0);
+    assert_eq!(
+        config.get_withdrawal(Level::Auther, 0, &totals).unwrap(),
+        13000
+    );
+    // Level B is full, the 1000 hundredths left in its pool go to the Platform
+    assert_eq!(
+        config.get_withdrawal(Level::Platform, 0, &totals).unwrap(),
+        7010
+    );
+    let mut open_config = config.clone();
+    open_config.nums[1] += 1;
+    assert_eq!(
+        open_config
+            .get_withdrawal(Level::Platform, 0, &totals)
+            .unwrap(),
+        7000
+    );
+    assert!(config.get_withdrawal(Level::A, 4601, &totals).is_err());
+
+    let totals = TotalAmounts {
+        a: u128::MAX / 2,
+        b: 0,
+        c: 0,
+        d: 0,
     };
+    assert!(config.get_withdrawal(Level::Platform, 0, &totals).is_err());
+}
 
-    let tx = context.complete_tx(tx);
+#[test]
+fn test_profit_shares() {
+    let mut context = new_context();
+    let mut config = ProfitConfig::from(&def_account_book_cell_data(&mut context));
+
+    let mut totals = TotalAmounts {
+        a: 0,
+        b: 0,
+        c: 0,
+        d: 0,
+    };
+    // (level, share index) of the members in the order they joined
+    let mut members: Vec<(Level, u128)> = Vec::new();
+    let mut entitlements: Vec<u128> = Vec::new();
+    let levels = [
+        Level::B,
+        Level::D,
+        Level::A,
+        Level::C,
+        Level::B,
+        Level::A,
+        Level::D,
+        Level::C,
+        Level::B,
+        Level::A,
+    ];
+    for (i, level) in levels.into_iter().enumerate() {
+        let old_totals = totals.clone();
+        totals.add(97 + 13 * i as u128, level).unwrap();
+        members.push((level, profit::share_index(&config.shares, level).unwrap()));
+        let old_config = config.clone();
+        if let Some(count) = config.counts.get_mut(level as usize - 1) {
+            *count += 1;
+        }
+        config.shares = config
+            .next_shares(&old_config, &old_totals, &totals)
+            .unwrap();
+
+        // A share never shrinks when others join
+        let new_entitlements: Vec<u128> = members
+            .iter()
+            .map(|(level, share_index)| config.member(*level, *share_index).unwrap())
+            .collect();
+        assert!(entitlements
+            .iter()
+            .zip(&new_entitlements)
+            .all(|(old, new)| old <= new));
+        entitlements = new_entitlements;
+
+        // All is paid out, except the pools kept for the members of the open levels. Level A
+        // is sold out by its third member.
+        let reserved: u128 = (0..3)
+            .filter(|index| !config.is_closed(*index))
+            .map(|index| config.shares[index].pool / 100)
+            .sum();
+        let distributed = config.platform(&totals).unwrap()
+            + config.auther(&totals).unwrap()
+            + entitlements.iter().sum::<u128>();
+        assert_eq!(distributed + reserved, totals.total().unwrap());
+    }
+}
+
+fn build_claim_tx(
+    context: &mut Context,
+    key: SmtKey,
+    old_amount: u128,
+    amount: u128,
+) -> TransactionView {
+    build_claim_tx_in(context, false, key, old_amount, amount)
+}
+
+// Claimed in xUDT, or in CKB if `ckb`
+fn build_claim_tx_in(
+    context: &mut Context,
+    ckb: bool,
+    key: SmtKey,
+    old_amount: u128,
+    amount: u128,
+) -> TransactionView {
+    let auther_lock_script = build_always_suc_script(context, &[0x33; 32]);
+    let platform_lock_script = build_always_suc_script(context, &[0x44; 32]);
+
+    let account_book_cell_data = def_account_book_cell_data(context)
+        .as_builder()
+        .auther_id(auther_lock_script.calc_script_hash())
+        .platform_id(platform_lock_script.calc_script_hash())
+        .build();
+    let (tx, _) = build_account_book_withdrawal(
+        context,
+        ckb,
+        account_book_cell_data,
+        &[(key.clone(), SmtValue::new(old_amount), amount)],
+    );
+
+    let lock_script = match key {
+        SmtKey::Auther => auther_lock_script,
+        _ => platform_lock_script,
+    };
+    // The payee signs the claim and pays the fee
+    let input_cell = {
+        let (cell, _) = build_asset_cell(context, lock_script.clone(), true, 1000);
+        context.create_cell(cell, Default::default())
+    };
+    let (output_cell, output_data) = build_asset_cell(context, lock_script, ckb, amount);
+    let tx = tx
+        .as_advanced_builder()
+        .input(build_input(input_cell))
+        .output(output_cell)
+        .output_data(output_data.pack())
+        .witness(Default::default())
+        .build();
+
+    context.complete_tx(tx)
+}
+
+#[test]
+fn test_auther_claim() {
+    let mut context = new_context();
+    // (10000 * 80 + 20000 * 20 + 5000 * 20) / 100
+    let tx = build_claim_tx(&mut context, SmtKey::Auther, 122, 13000 - 122);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect("pass");
+}
+
+#[test]
+fn test_platform_claim() {
+    let mut context = new_context();
+    // (10000 * 20 + 20000 * 20 + 5000 * 20) / 100, the other columns have no remainder
+    let tx = build_claim_tx(&mut context, SmtKey::Platform, 0, 7000);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect("pass");
+}
+
+#[test]
+fn test_auther_claim_ckb() {
+    let mut context = new_context();
+    let tx = build_claim_tx_in(&mut context, true, SmtKey::Auther, 122, 13000 - 122);
     verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect("pass");
 }
+
+#[test]
+fn test_auther_claim_ckb_elsewhere() {
+    let mut context = new_context();
+    let tx = build_claim_tx_in(&mut context, true, SmtKey::Auther, 122, 13000 - 122);
+
+    // The Auther puts in more than the payout and takes it back, the payout goes to another lock
+    let auther_lock_script = build_always_suc_script(&mut context, &[0x33; 32]);
+    let other_lock_script = build_always_suc_script(&mut context, &[0x99; 32]);
+    let outputs: Vec<CellOutput> = tx
+        .outputs()
+        .into_iter()
+        .map(|output| {
+            if output.lock() == auther_lock_script {
+                output.as_builder().lock(other_lock_script.clone()).build()
+            } else {
+                output
+            }
+        })
+        .collect();
+    let (auther_cell, _) = build_asset_cell(&mut context, auther_lock_script, true, 20000);
+    let auther_input = build_input(context.create_cell(auther_cell.clone(), Default::default()));
+    let tx = tx
+        .as_advanced_builder()
+        .set_outputs(outputs)
+        .input(auther_input)
+        .output(auther_cell)
+        .output_data(Default::default())
+        .witness(Default::default())
+        .build();
+    let tx = context.complete_tx(tx);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("payout elsewhere");
+}
+
+#[test]
+fn test_platform_claim_exceeds_entitlement() {
+    let mut context = new_context();
+    let tx = build_claim_tx(&mut context, SmtKey::Platform, 0, 7001);
+    verify_and_dump_failed_tx(&context, &tx, MAX_CYCLES).expect_err("claim exceeds");
+}